@@ -0,0 +1,99 @@
+//! algebraic structures shared by the data structures of this crate
+
+use std::{
+    marker::PhantomData,
+    ops::{Add, BitXor, Neg},
+};
+
+/// Binary operation which applies,
+///
+/// - Associativity: op(op(x, y), z) == op(x, op(y, z)),
+/// - Commutativity: op(x, y) == op(y, x),
+/// - Identity: op(x, e) == x,
+/// - Inverse: op(x, inv(x)) == e,
+///
+/// For example...
+/// - Additive
+/// - Xor
+pub trait AbelianGroup {
+    type S: Clone + PartialEq;
+    fn identity() -> Self::S;
+    fn binary_operation(x: &Self::S, y: &Self::S) -> Self::S;
+    fn inverse(x: &Self::S) -> Self::S;
+}
+
+/// `S::default()` is regarded as zero
+pub struct Additive<S>(PhantomData<S>);
+
+impl<S: Clone + PartialEq + Default + Add<Output = S> + Neg<Output = S>> AbelianGroup
+    for Additive<S>
+{
+    type S = S;
+
+    fn identity() -> Self::S {
+        S::default()
+    }
+
+    fn binary_operation(x: &Self::S, y: &Self::S) -> Self::S {
+        x.clone() + y.clone()
+    }
+
+    fn inverse(x: &Self::S) -> Self::S {
+        -x.clone()
+    }
+}
+
+/// `S::default()` is regarded as zero
+pub struct Xor<S>(PhantomData<S>);
+
+impl<S: Clone + PartialEq + Default + BitXor<Output = S>> AbelianGroup for Xor<S> {
+    type S = S;
+
+    fn identity() -> Self::S {
+        S::default()
+    }
+
+    fn binary_operation(x: &Self::S, y: &Self::S) -> Self::S {
+        x.clone() ^ y.clone()
+    }
+
+    fn inverse(x: &Self::S) -> Self::S {
+        x.clone()
+    }
+}
+
+/// Binary operation which applies,
+///
+/// - Associativity: op(op(x, y), z) == op(x, op(y, z)),
+/// - Identity: op(x, e) == op(e, x) == x,
+///
+/// commutativity is not required, unlike `AbelianGroup`
+pub trait Monoid {
+    type S: Clone;
+    fn identity() -> Self::S;
+    fn binary_operation(x: &Self::S, y: &Self::S) -> Self::S;
+}
+
+impl<S: Clone + Default + Add<Output = S>> Monoid for Additive<S> {
+    type S = S;
+
+    fn identity() -> Self::S {
+        S::default()
+    }
+
+    fn binary_operation(x: &Self::S, y: &Self::S) -> Self::S {
+        x.clone() + y.clone()
+    }
+}
+
+impl<S: Clone + Default + BitXor<Output = S>> Monoid for Xor<S> {
+    type S = S;
+
+    fn identity() -> Self::S {
+        S::default()
+    }
+
+    fn binary_operation(x: &Self::S, y: &Self::S) -> Self::S {
+        x.clone() ^ y.clone()
+    }
+}
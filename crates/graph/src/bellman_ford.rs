@@ -0,0 +1,223 @@
+use std::{collections::VecDeque, ops::Add};
+
+use super::{dijkstra::is_shorter, DirectedWeightedListGraph, Weigh, Weighted};
+
+/// shortest distance on a graph which may have negative cycles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distance<S> {
+    Unreachable,
+    Finite(S),
+    /// reachable from a negative cycle
+    NegInf,
+}
+
+impl<S> Distance<S> {
+    pub fn finite(self) -> Option<S> {
+        match self {
+            Distance::Finite(d) => Some(d),
+            _ => None,
+        }
+    }
+}
+
+impl<S> DirectedWeightedListGraph<S>
+where
+    Weighted<S>: Weigh<(usize, S)>,
+    S: Clone + Add<Output = S> + Ord + Default,
+{
+    /// shortest distances from `start` by Bellman-Ford algorithm in `O(nm)`
+    ///
+    /// `S::default()` is regarded as zero
+    pub fn bellman_ford(&self, start: usize) -> Vec<Distance<S>> {
+        let n = self.len();
+        let mut dist: Vec<Option<S>> = vec![None; n];
+        dist[start] = Some(S::default());
+        let mut seeds = Vec::new();
+        for i in 0..n {
+            for u in 0..n {
+                let du = match dist[u].clone() {
+                    Some(du) => du,
+                    None => continue,
+                };
+                for (v, w) in self.inner[u].iter() {
+                    let cand = du.clone() + w.clone();
+                    if is_shorter(&cand, &dist[*v]) {
+                        dist[*v] = Some(cand);
+                        if i == n - 1 {
+                            seeds.push(*v);
+                        }
+                    }
+                }
+            }
+        }
+        self.mark_neg_inf(dist, seeds)
+    }
+
+    /// shortest distances from `start` by SPFA (queue-based Bellman-Ford)
+    ///
+    /// faster than `bellman_ford` on average, but still `O(nm)` in the worst case
+    pub fn spfa(&self, start: usize) -> Vec<Distance<S>> {
+        let n = self.len();
+        let mut dist: Vec<Option<S>> = vec![None; n];
+        // number of edges on the current shortest path to each vertex
+        let mut edges = vec![0; n];
+        let mut in_queue = vec![false; n];
+        let mut seeds = Vec::new();
+        let mut queue = VecDeque::new();
+        dist[start] = Some(S::default());
+        queue.push_back(start);
+        in_queue[start] = true;
+        while let Some(u) = queue.pop_front() {
+            in_queue[u] = false;
+            let du = dist[u].clone().unwrap();
+            for (v, w) in self.inner[u].iter() {
+                let cand = du.clone() + w.clone();
+                if is_shorter(&cand, &dist[*v]) {
+                    dist[*v] = Some(cand);
+                    edges[*v] = edges[u] + 1;
+                    // a shorter walk with n edges repeats a vertex, so it goes around a negative cycle
+                    if edges[*v] >= n {
+                        seeds.push(*v);
+                    } else if !in_queue[*v] {
+                        in_queue[*v] = true;
+                        queue.push_back(*v);
+                    }
+                }
+            }
+        }
+        self.mark_neg_inf(dist, seeds)
+    }
+
+    /// vertices reachable from `seeds` become `NegInf`
+    fn mark_neg_inf(&self, dist: Vec<Option<S>>, seeds: Vec<usize>) -> Vec<Distance<S>> {
+        let mut res: Vec<_> = dist
+            .into_iter()
+            .map(|d| match d {
+                Some(d) => Distance::Finite(d),
+                None => Distance::Unreachable,
+            })
+            .collect();
+        let mut stack = Vec::new();
+        for v in seeds {
+            if res[v] != Distance::NegInf {
+                res[v] = Distance::NegInf;
+                stack.push(v);
+            }
+        }
+        while let Some(u) = stack.pop() {
+            for &(v, _) in self.inner[u].iter() {
+                if res[v] != Distance::NegInf {
+                    res[v] = Distance::NegInf;
+                    stack.push(v);
+                }
+            }
+        }
+        res
+    }
+
+    /// one of the negative cycles in the whole graph, as a sequence of vertices
+    ///
+    /// `0[0] -> 0[1] -> ... -> 0[k - 1] -> 0[0]`
+    pub fn negative_cycle(&self) -> Option<Vec<usize>> {
+        let n = self.len();
+        // as if there is a virtual start with edges of weight zero to every vertex
+        let mut dist = vec![S::default(); n];
+        let mut parent = vec![None; n];
+        let mut last = None;
+        for _ in 0..n {
+            last = None;
+            for u in 0..n {
+                for (v, w) in self.inner[u].iter() {
+                    let cand = dist[u].clone() + w.clone();
+                    if cand < dist[*v] {
+                        dist[*v] = cand;
+                        parent[*v] = Some(u);
+                        last = Some(*v);
+                    }
+                }
+            }
+            last?;
+        }
+        // go back n times to surely be on the cycle
+        let mut x = last?;
+        for _ in 0..n {
+            x = parent[x].unwrap();
+        }
+        let mut cycle = vec![x];
+        let mut v = parent[x].unwrap();
+        while v != x {
+            cycle.push(v);
+            v = parent[v].unwrap();
+        }
+        cycle.reverse();
+        Some(cycle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::DirectedWeightedListGraph;
+    use super::Distance::*;
+
+    #[test]
+    fn bellman_ford() {
+        let edges = &[(0, 1, 4), (0, 2, 5), (1, 2, -3), (2, 3, 2), (4, 0, 1)];
+        let g = DirectedWeightedListGraph::from_edges(5, edges);
+        let expected = vec![Finite(0), Finite(4), Finite(1), Finite(3), Unreachable];
+        assert_eq!(g.bellman_ford(0), expected);
+        assert_eq!(g.spfa(0), expected);
+        assert_eq!(g.negative_cycle(), None);
+    }
+
+    #[test]
+    fn negative_cycle() {
+        // 1 -> 2 -> 3 -> 1 has weight -1
+        let edges = &[
+            (0, 1, 1),
+            (1, 2, 2),
+            (2, 3, -4),
+            (3, 1, 1),
+            (3, 4, 0),
+            (0, 5, 3),
+            (6, 0, 1),
+        ];
+        let g = DirectedWeightedListGraph::from_edges(7, edges);
+        let expected = vec![
+            Finite(0),
+            NegInf,
+            NegInf,
+            NegInf,
+            NegInf,
+            Finite(3),
+            Unreachable,
+        ];
+        assert_eq!(g.bellman_ford(0), expected);
+        assert_eq!(g.spfa(0), expected);
+        assert_eq!(g.bellman_ford(5)[5].finite(), Some(0));
+
+        let mut cycle = g.negative_cycle().unwrap();
+        let p = cycle.iter().position(|&v| v == 1).unwrap();
+        cycle.rotate_left(p);
+        assert_eq!(cycle, vec![1, 2, 3]);
+
+        let g = DirectedWeightedListGraph::from_edges(2, &[(1, 1, -1i64)]);
+        assert_eq!(g.negative_cycle(), Some(vec![1]));
+        assert_eq!(g.bellman_ford(0), vec![Finite(0), Unreachable]);
+        assert_eq!(g.spfa(1), vec![Unreachable, NegInf]);
+    }
+
+    #[test]
+    fn single_vertex() {
+        let g = DirectedWeightedListGraph::<i64>::from_edges(1, &[]);
+        assert_eq!(g.bellman_ford(0), vec![Finite(0)]);
+        assert_eq!(g.spfa(0), g.bellman_ford(0));
+
+        let g = DirectedWeightedListGraph::from_edges(1, &[(0, 0, 2i64)]);
+        assert_eq!(g.bellman_ford(0), vec![Finite(0)]);
+        assert_eq!(g.spfa(0), g.bellman_ford(0));
+
+        let g = DirectedWeightedListGraph::from_edges(1, &[(0, 0, -2i64)]);
+        assert_eq!(g.bellman_ford(0), vec![NegInf]);
+        assert_eq!(g.spfa(0), g.bellman_ford(0));
+    }
+}
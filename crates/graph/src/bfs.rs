@@ -1,15 +1,21 @@
 use std::collections::VecDeque;
 
-use super::{Direct, UnweightedListGraph};
+use super::{
+    grid::{GridGraph, Pos},
+    Direct, UnweightedListGraph,
+};
 
 pub trait Bfsable<'a, V, N>: Sized {
     fn bfs(&'a self, start: N) -> Bfs<'a, Self, V, N>;
+    /// BFS whose every vertex in `starts` has distance 0
+    fn multi_source_bfs(&'a self, starts: &[N]) -> Bfs<'a, Self, V, N>;
 }
 
 pub struct Bfs<'a, G: Bfsable<'a, V, N>, V, N> {
     graph: &'a G,
     distance: V,
-    start: N,
+    parent: V,
+    starts: Vec<N>,
     queue: VecDeque<(N, Option<N>)>,
 }
 
@@ -19,32 +25,32 @@ impl<'a, D: Direct> Iterator for Bfs<'a, UnweightedListGraph<D>, Vec<Option<usiz
     type Item = Link<usize>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some((u, prev)) = self.queue.pop_front() {
-            let distance = self.distance.clone();
-            for &neighbor in self.graph.inner[u]
-                .iter()
-                .filter(|&&x| distance[x].is_none())
-            {
+        while let Some((u, prev)) = self.queue.pop_front() {
+            let d = self.distance[u].unwrap();
+            for &neighbor in self.graph.inner[u].iter() {
                 if self.distance[neighbor].is_none() {
-                    self.distance[neighbor] = Some(self.distance[u].unwrap() + 1);
+                    self.distance[neighbor] = Some(d + 1);
+                    self.parent[neighbor] = Some(u);
                     self.queue.push_back((neighbor, Some(u)));
                 }
             }
 
             if let Some(prev) = prev {
-                Some((prev, u))
-            } else {
-                self.next()
+                return Some((prev, u));
             }
-        } else {
-            None
         }
+        None
     }
 }
 
 impl<'a, D: Direct> Bfs<'a, UnweightedListGraph<D>, Vec<Option<usize>>, usize> {
+    /// first vertex of starts
     pub fn start(&self) -> usize {
-        self.start
+        self.starts[0]
+    }
+
+    pub fn starts(&self) -> &[usize] {
+        &self.starts
     }
 
     pub fn find<F: Fn(usize) -> bool>(&mut self, f: F) -> Option<usize> {
@@ -57,15 +63,33 @@ impl<'a, D: Direct> Bfs<'a, UnweightedListGraph<D>, Vec<Option<usize>>, usize> {
     }
 
     pub fn dist(&mut self, goal: usize) -> Option<usize> {
-        if self.start == goal {
-            return Some(0);
-        }
-        for (_, to) in self.into_iter() {
-            if to == goal {
-                return self.distance[goal];
-            }
+        while self.distance[goal].is_none() && self.next().is_some() {}
+        self.distance[goal]
+    }
+
+    /// run to the end, and return distance from the nearest start for each vertex
+    pub fn distances(&mut self) -> &[Option<usize>] {
+        self.by_ref().for_each(drop);
+        &self.distance
+    }
+
+    /// run to the end, and return parent on the BFS tree for each vertex
+    ///
+    /// starts and unreachable vertices have `None`
+    pub fn parents(&mut self) -> &[Option<usize>] {
+        self.by_ref().for_each(drop);
+        &self.parent
+    }
+
+    /// shortest path from one of starts to `goal`, both ends inclusive
+    pub fn path_to(&mut self, goal: usize) -> Option<Vec<usize>> {
+        self.dist(goal)?;
+        let mut path = vec![goal];
+        while let Some(p) = self.parent[*path.last().unwrap()] {
+            path.push(p);
         }
-        None
+        path.reverse();
+        Some(path)
     }
 }
 
@@ -73,18 +97,122 @@ impl<'a, D: Direct> Bfs<'a, UnweightedListGraph<D>, Vec<Option<usize>>, usize> {
 
 impl<'a, D: Direct> Bfsable<'a, Vec<Option<usize>>, usize> for UnweightedListGraph<D> {
     fn bfs(&'a self, start: usize) -> Bfs<'a, Self, Vec<Option<usize>>, usize> {
+        self.multi_source_bfs(&[start])
+    }
+
+    fn multi_source_bfs(&'a self, starts: &[usize]) -> Bfs<'a, Self, Vec<Option<usize>>, usize> {
+        assert!(!starts.is_empty(), "at least one start is needed");
         let mut distance = vec![None; self.len()];
-        distance[start] = Some(0);
         let mut queue = VecDeque::new();
-        queue.push_back((start, None));
+        for &start in starts {
+            if distance[start].is_none() {
+                distance[start] = Some(0);
+                queue.push_back((start, None));
+            }
+        }
         Bfs {
             graph: self,
             distance,
-            start,
+            parent: vec![None; self.len()],
+            starts: starts.to_vec(),
             queue,
         }
     }
 }
+
+impl<'a, C> Iterator for Bfs<'a, GridGraph<C>, Vec<Vec<Option<usize>>>, Pos> {
+    type Item = Link<Pos>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((u, prev)) = self.queue.pop_front() {
+            let d = self.distance[u.0][u.1].unwrap();
+            for (r, c) in self.graph.neighbors(u) {
+                if self.distance[r][c].is_none() {
+                    self.distance[r][c] = Some(d + 1);
+                    self.parent[r][c] = Some(self.graph.index(u));
+                    self.queue.push_back(((r, c), Some(u)));
+                }
+            }
+
+            if let Some(prev) = prev {
+                return Some((prev, u));
+            }
+        }
+        None
+    }
+}
+
+impl<'a, C> Bfs<'a, GridGraph<C>, Vec<Vec<Option<usize>>>, Pos> {
+    /// first cell of starts
+    pub fn start(&self) -> Pos {
+        self.starts[0]
+    }
+
+    pub fn starts(&self) -> &[Pos] {
+        &self.starts
+    }
+
+    pub fn dist(&mut self, goal: Pos) -> Option<usize> {
+        while self.distance[goal.0][goal.1].is_none() && self.next().is_some() {}
+        self.distance[goal.0][goal.1]
+    }
+
+    /// run to the end, and return distance from the nearest start for each cell
+    pub fn distances(&mut self) -> &[Vec<Option<usize>>] {
+        self.by_ref().for_each(drop);
+        &self.distance
+    }
+
+    /// run to the end, and return parent on the BFS tree for each cell
+    pub fn parents(&mut self) -> Vec<Vec<Option<Pos>>> {
+        self.by_ref().for_each(drop);
+        let graph = self.graph;
+        self.parent
+            .iter()
+            .map(|row| row.iter().map(|p| p.map(|i| graph.pos(i))).collect())
+            .collect()
+    }
+
+    /// shortest path from one of starts to `goal`, both ends inclusive
+    pub fn path_to(&mut self, goal: Pos) -> Option<Vec<Pos>> {
+        self.dist(goal)?;
+        let mut path = vec![goal];
+        while let Some(i) = {
+            let &(r, c) = path.last().unwrap();
+            self.parent[r][c]
+        } {
+            path.push(self.graph.pos(i));
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<'a, C> Bfsable<'a, Vec<Vec<Option<usize>>>, Pos> for GridGraph<C> {
+    fn bfs(&'a self, start: Pos) -> Bfs<'a, Self, Vec<Vec<Option<usize>>>, Pos> {
+        self.multi_source_bfs(&[start])
+    }
+
+    fn multi_source_bfs(&'a self, starts: &[Pos]) -> Bfs<'a, Self, Vec<Vec<Option<usize>>>, Pos> {
+        assert!(!starts.is_empty(), "at least one start is needed");
+        let mut distance = vec![vec![None; self.width()]; self.height()];
+        let mut queue = VecDeque::new();
+        for &(r, c) in starts {
+            if distance[r][c].is_none() {
+                distance[r][c] = Some(0);
+                queue.push_back(((r, c), None));
+            }
+        }
+        Bfs {
+            graph: self,
+            distance,
+            parent: vec![vec![None; self.width()]; self.height()],
+            starts: starts.to_vec(),
+            queue,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::UndirectedUnweightedListGraph;
@@ -116,4 +244,44 @@ mod tests {
         assert_eq!(g.bfs(2).dist(4), Some(1));
         assert_eq!(g.bfs(1).dist(5), None);
     }
+
+    #[test]
+    fn distances_and_paths() {
+        let edges = &[(0, 1), (1, 2), (1, 3), (2, 4)];
+        let g = UndirectedUnweightedListGraph::from_edges(6, edges);
+
+        let mut bfs = g.bfs(0);
+        assert_eq!(
+            bfs.distances(),
+            &[Some(0), Some(1), Some(2), Some(2), Some(3), None]
+        );
+        assert_eq!(
+            bfs.parents(),
+            &[None, Some(0), Some(1), Some(1), Some(2), None]
+        );
+        assert_eq!(g.bfs(0).path_to(4), Some(vec![0, 1, 2, 4]));
+        assert_eq!(g.bfs(3).path_to(3), Some(vec![3]));
+        assert_eq!(g.bfs(0).path_to(5), None);
+    }
+
+    #[test]
+    fn multi_source() {
+        let edges = &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5)];
+        let g = UndirectedUnweightedListGraph::from_edges(6, edges);
+
+        let mut bfs = g.multi_source_bfs(&[0, 5]);
+        assert_eq!(
+            bfs.distances(),
+            &[Some(0), Some(1), Some(2), Some(2), Some(1), Some(0)]
+        );
+        assert_eq!(g.multi_source_bfs(&[0, 5]).path_to(3), Some(vec![5, 4, 3]));
+    }
+
+    #[test]
+    fn long_path() {
+        let n = 1_000_000;
+        let edges: Vec<_> = (0..n - 1).map(|i| (i, i + 1)).collect();
+        let g = UndirectedUnweightedListGraph::from_edges(n, &edges);
+        assert_eq!(g.bfs(0).dist(n - 1), Some(n - 1));
+    }
 }
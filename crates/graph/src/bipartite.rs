@@ -0,0 +1,334 @@
+use std::collections::VecDeque;
+
+use super::UndirectedUnweightedListGraph;
+
+/// cycle of odd length which proves the graph is not bipartite
+///
+/// `0[0] - 0[1] - ... - 0[k - 1] - 0[0]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OddCycle(pub Vec<usize>);
+
+impl UndirectedUnweightedListGraph {
+    /// 2-coloring where every edge connects different colors, or an odd cycle
+    ///
+    /// the smallest vertex of each connected component is colored `false`
+    pub fn bipartition(&self) -> Result<Vec<bool>, OddCycle> {
+        let n = self.len();
+        let mut color: Vec<Option<bool>> = vec![None; n];
+        let mut parent = vec![None; n];
+        let mut queue = VecDeque::new();
+        for root in 0..n {
+            if color[root].is_some() {
+                continue;
+            }
+            color[root] = Some(false);
+            queue.push_back(root);
+            while let Some(u) = queue.pop_front() {
+                let cu = color[u].unwrap();
+                for &v in self.inner[u].iter() {
+                    match color[v] {
+                        None => {
+                            color[v] = Some(!cu);
+                            parent[v] = Some(u);
+                            queue.push_back(v);
+                        }
+                        Some(cv) if cv == cu => return Err(odd_cycle(u, v, &parent)),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Ok(color.into_iter().map(Option::unwrap).collect())
+    }
+}
+
+/// cycle made of the edge `u - v` and the BFS tree paths to their lowest common ancestor
+fn odd_cycle(u: usize, v: usize, parent: &[Option<usize>]) -> OddCycle {
+    let to_root = |mut x: usize| {
+        let mut path = vec![x];
+        while let Some(p) = parent[x] {
+            path.push(p);
+            x = p;
+        }
+        path
+    };
+    let mut pu = to_root(u);
+    let mut pv = to_root(v);
+    // drop the common part except for the lowest common ancestor
+    while pu.len() >= 2 && pv.len() >= 2 && pu[pu.len() - 2] == pv[pv.len() - 2] {
+        pu.pop();
+        pv.pop();
+    }
+    pv.pop();
+    pv.reverse();
+    pu.extend(pv);
+    OddCycle(pu)
+}
+
+/// maximum matching on bipartite graph by Hopcroft-Karp algorithm in `O(m sqrt(n))`
+///
+/// ```ignore
+/// let mut bm = BipartiteMatching::new(2, 2);
+/// bm.add_edge(0, 0);
+/// bm.add_edge(1, 0);
+/// bm.add_edge(1, 1);
+/// assert_eq!(bm.max_matching(), vec![(0, 0), (1, 1)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BipartiteMatching {
+    left: usize,
+    right: usize,
+    adj: Vec<Vec<usize>>,
+    match_left: Vec<Option<usize>>,
+    match_right: Vec<Option<usize>>,
+}
+
+impl BipartiteMatching {
+    pub fn new(left: usize, right: usize) -> Self {
+        Self {
+            left,
+            right,
+            adj: vec![Vec::new(); left],
+            match_left: vec![None; left],
+            match_right: vec![None; right],
+        }
+    }
+
+    /// add edge between `l`-th vertex on the left and `r`-th vertex on the right
+    pub fn add_edge(&mut self, l: usize, r: usize) {
+        assert!(l < self.left && r < self.right);
+        self.adj[l].push(r);
+    }
+
+    /// pairs `(l, r)` of a maximum matching, sorted by `l`
+    pub fn max_matching(&mut self) -> Vec<(usize, usize)> {
+        while self.augment() {}
+        (0..self.left)
+            .filter_map(|l| self.match_left[l].map(|r| (l, r)))
+            .collect()
+    }
+
+    /// one phase of Hopcroft-Karp, returns whether the matching grows
+    fn augment(&mut self) -> bool {
+        const INF: usize = usize::MAX;
+        let mut dist: Vec<_> = self
+            .match_left
+            .iter()
+            .map(|m| if m.is_none() { 0 } else { INF })
+            .collect();
+        let mut queue: VecDeque<_> = (0..self.left).filter(|&l| dist[l] == 0).collect();
+        // layer of left vertices adjacent to the nearest free right vertices,
+        // only shortest augmenting paths ending there are used
+        let mut last = INF;
+        while let Some(l) = queue.pop_front() {
+            if dist[l] > last {
+                break;
+            }
+            for &r in self.adj[l].iter() {
+                match self.match_right[r] {
+                    None => last = dist[l],
+                    Some(l2) if dist[l2] == INF && dist[l] < last => {
+                        dist[l2] = dist[l] + 1;
+                        queue.push_back(l2);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if last == INF {
+            return false;
+        }
+
+        // iterative DFS along the layers
+        let mut iter = vec![0; self.left];
+        let mut stack = Vec::new();
+        for root in 0..self.left {
+            if self.match_left[root].is_some() {
+                continue;
+            }
+            stack.push(root);
+            while let Some(&l) = stack.last() {
+                let r = match self.adj[l].get(iter[l]) {
+                    Some(&r) => r,
+                    None => {
+                        // dead end
+                        dist[l] = INF;
+                        stack.pop();
+                        if let Some(&p) = stack.last() {
+                            iter[p] += 1;
+                        }
+                        continue;
+                    }
+                };
+                match self.match_right[r] {
+                    None if dist[l] == last => {
+                        for &x in stack.iter() {
+                            let y = self.adj[x][iter[x]];
+                            self.match_left[x] = Some(y);
+                            self.match_right[y] = Some(x);
+                        }
+                        stack.clear();
+                    }
+                    Some(l2) if dist[l] < last && dist[l2] == dist[l] + 1 => stack.push(l2),
+                    _ => iter[l] += 1,
+                }
+            }
+        }
+        true
+    }
+
+    /// vertices reachable from unmatched left vertices by alternating paths
+    fn alternating_reachable(&mut self) -> (Vec<bool>, Vec<bool>) {
+        self.max_matching();
+        let mut seen_left = vec![false; self.left];
+        let mut seen_right = vec![false; self.right];
+        let mut stack: Vec<usize> = (0..self.left)
+            .filter(|&l| self.match_left[l].is_none())
+            .collect();
+        for &l in stack.iter() {
+            seen_left[l] = true;
+        }
+        while let Some(l) = stack.pop() {
+            for &r in self.adj[l].iter() {
+                if seen_right[r] {
+                    continue;
+                }
+                seen_right[r] = true;
+                if let Some(l2) = self.match_right[r] {
+                    if !seen_left[l2] {
+                        seen_left[l2] = true;
+                        stack.push(l2);
+                    }
+                }
+            }
+        }
+        (seen_left, seen_right)
+    }
+
+    /// `(left vertices, right vertices)` of a minimum vertex cover, whose size equals to the maximum matching
+    pub fn min_vertex_cover(&mut self) -> (Vec<usize>, Vec<usize>) {
+        let (seen_left, seen_right) = self.alternating_reachable();
+        (
+            (0..self.left).filter(|&l| !seen_left[l]).collect(),
+            (0..self.right).filter(|&r| seen_right[r]).collect(),
+        )
+    }
+
+    /// `(left vertices, right vertices)` of a maximum independent set, the complement of `min_vertex_cover`
+    pub fn max_independent_set(&mut self) -> (Vec<usize>, Vec<usize>) {
+        let (seen_left, seen_right) = self.alternating_reachable();
+        (
+            (0..self.left).filter(|&l| seen_left[l]).collect(),
+            (0..self.right).filter(|&r| !seen_right[r]).collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::super::{random::test_rng, UndirectedUnweightedListGraph};
+    use super::{BipartiteMatching, OddCycle};
+
+    /// size of a maximum matching by simple augmenting paths
+    fn naive_matching(adj: &[Vec<usize>], right: usize) -> usize {
+        fn dfs(l: usize, adj: &[Vec<usize>], seen: &mut [bool], mr: &mut [Option<usize>]) -> bool {
+            for &r in adj[l].iter() {
+                if !seen[r] {
+                    seen[r] = true;
+                    let free = match mr[r] {
+                        None => true,
+                        Some(l2) => dfs(l2, adj, seen, mr),
+                    };
+                    if free {
+                        mr[r] = Some(l);
+                        return true;
+                    }
+                }
+            }
+            false
+        }
+        let mut mr = vec![None; right];
+        (0..adj.len())
+            .filter(|&l| dfs(l, adj, &mut vec![false; right], &mut mr))
+            .count()
+    }
+
+    #[test]
+    fn bipartition() {
+        let edges = &[(0, 1), (1, 2), (2, 3), (3, 0), (4, 5)];
+        let g = UndirectedUnweightedListGraph::from_edges(7, edges);
+        assert_eq!(
+            g.bipartition(),
+            Ok(vec![false, true, false, true, false, true, false])
+        );
+
+        let edges = &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 1)];
+        let g = UndirectedUnweightedListGraph::from_edges(6, edges);
+        let OddCycle(cycle) = g.bipartition().unwrap_err();
+        assert_eq!(cycle.len() % 2, 1);
+        for i in 0..cycle.len() {
+            let (u, v) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+            assert!(edges.contains(&(u, v)) || edges.contains(&(v, u)));
+        }
+
+        let g = UndirectedUnweightedListGraph::from_edges(1, &[(0, 0)]);
+        assert_eq!(g.bipartition(), Err(OddCycle(vec![0])));
+    }
+
+    #[test]
+    fn matching() {
+        let mut bm = BipartiteMatching::new(4, 4);
+        for &(l, r) in &[(0, 0), (0, 1), (1, 0), (2, 1), (2, 2), (3, 2)] {
+            bm.add_edge(l, r);
+        }
+        let m = bm.max_matching();
+        assert_eq!(m.len(), 3);
+        let (cl, cr) = bm.min_vertex_cover();
+        assert_eq!(cl.len() + cr.len(), 3);
+        for &(l, r) in &[(0, 0), (0, 1), (1, 0), (2, 1), (2, 2), (3, 2)] {
+            assert!(cl.contains(&l) || cr.contains(&r));
+        }
+        let (il, ir) = bm.max_independent_set();
+        assert_eq!(il.len() + ir.len(), 8 - 3);
+        assert!(ir.contains(&3));
+    }
+
+    #[test]
+    fn long_augmenting_path() {
+        // left i is adjacent to right i and i + 1, and the greedy matching is bad
+        let n = 100_000;
+        let mut bm = BipartiteMatching::new(n, n);
+        for i in 0..n {
+            if i + 1 < n {
+                bm.add_edge(i, i + 1);
+            }
+            bm.add_edge(i, i);
+        }
+        assert_eq!(bm.max_matching().len(), n);
+    }
+
+    #[test]
+    fn random_matching() {
+        let mut rng = test_rng(38);
+        for _ in 0..300 {
+            let left = rng.gen_range(1, 12);
+            let right = rng.gen_range(1, 12);
+            let mut adj = vec![Vec::new(); left];
+            let mut bm = BipartiteMatching::new(left, right);
+            for _ in 0..rng.gen_range(0, 30) {
+                let (l, r) = (rng.gen_range(0, left), rng.gen_range(0, right));
+                adj[l].push(r);
+                bm.add_edge(l, r);
+            }
+            let m = bm.max_matching();
+            assert_eq!(m.len(), naive_matching(&adj, right));
+            assert!(m.iter().all(|&(l, r)| adj[l].contains(&r)));
+            let mut rs: Vec<_> = m.iter().map(|&(_, r)| r).collect();
+            rs.sort_unstable();
+            rs.dedup();
+            assert_eq!(rs.len(), m.len());
+        }
+    }
+}
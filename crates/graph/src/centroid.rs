@@ -0,0 +1,167 @@
+use super::{ListGraph, Undirected, Weigh};
+
+/// tree whose root is a centroid of the whole tree,
+/// and children of each centroid are centroids of the components left by removing it
+///
+/// depth of the centroid tree is `O(log n)`
+pub struct CentroidTree {
+    root: usize,
+    parent: Vec<Option<usize>>,
+    level: Vec<usize>,
+    children: Vec<Vec<usize>>,
+}
+
+impl CentroidTree {
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parent[v]
+    }
+
+    /// depth on the centroid tree, the root is 0
+    pub fn level(&self, v: usize) -> usize {
+        self.level[v]
+    }
+
+    pub fn children(&self, v: usize) -> &[usize] {
+        &self.children[v]
+    }
+}
+
+impl<S, W: Weigh<S>> ListGraph<S, Undirected, W> {
+    /// centroid decomposition in `O(n log n)`, `self` must be a tree
+    pub fn centroid_decomposition(&self) -> CentroidTree {
+        let n = self.inner.len();
+        assert!(n > 0, "empty tree");
+        assert_eq!(self.edge_count() + 1, n, "not a tree");
+        let mut visited = vec![false; n];
+        let mut stack = vec![0];
+        visited[0] = true;
+        while let Some(u) = stack.pop() {
+            for v in self.neighbors(u) {
+                if !visited[v] {
+                    visited[v] = true;
+                    stack.push(v);
+                }
+            }
+        }
+        assert!(visited.iter().all(|&b| b), "not connected");
+        let mut removed = vec![false; n];
+        let mut par = vec![usize::MAX; n];
+        let mut size = vec![0; n];
+        let mut parent = vec![None; n];
+        let mut level = vec![0; n];
+        let mut children = vec![Vec::new(); n];
+        let mut root = None;
+
+        let mut component = Vec::new();
+        // (vertex in the component, centroid above it)
+        let mut tasks: Vec<(usize, Option<usize>)> = vec![(0, None)];
+        while let Some((entry, above)) = tasks.pop() {
+            component.clear();
+            par[entry] = usize::MAX;
+            component.push(entry);
+            let mut i = 0;
+            while i < component.len() {
+                let u = component[i];
+                i += 1;
+                for v in self.neighbors(u) {
+                    if !removed[v] && v != par[u] {
+                        par[v] = u;
+                        component.push(v);
+                    }
+                }
+            }
+            for &u in component.iter().rev() {
+                size[u] = 1;
+                for v in self.neighbors(u) {
+                    if !removed[v] && v != par[u] {
+                        size[u] += size[v];
+                    }
+                }
+            }
+
+            let total = component.len();
+            let mut c = entry;
+            while let Some(v) = self
+                .neighbors(c)
+                .find(|&v| !removed[v] && v != par[c] && size[v] * 2 > total)
+            {
+                c = v;
+            }
+
+            removed[c] = true;
+            parent[c] = above;
+            match above {
+                Some(p) => {
+                    level[c] = level[p] + 1;
+                    children[p].push(c);
+                }
+                None => root = Some(c),
+            }
+            for v in self.neighbors(c) {
+                if !removed[v] {
+                    tasks.push((v, Some(c)));
+                }
+            }
+        }
+
+        CentroidTree {
+            root: root.unwrap(),
+            parent,
+            level,
+            children,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        random::{self, test_rng},
+        UndirectedUnweightedListGraph,
+    };
+
+    #[test]
+    fn path() {
+        let n = 15;
+        let edges: Vec<_> = (0..n - 1).map(|i| (i, i + 1)).collect();
+        let g = UndirectedUnweightedListGraph::from_edges(n, &edges);
+        let ct = g.centroid_decomposition();
+        assert_eq!(ct.root(), 7);
+        assert_eq!(ct.parent(7), None);
+        assert_eq!(ct.parent(3), Some(7));
+        assert_eq!(ct.parent(11), Some(7));
+        assert_eq!(ct.level(0), 3);
+        assert!((0..n).all(|v| ct.level(v) <= 3));
+        let mut c = ct.children(7).to_vec();
+        c.sort_unstable();
+        assert_eq!(c, vec![3, 11]);
+    }
+
+    #[test]
+    fn subtrees_are_small() {
+        let n = 300;
+        let g = random::tree(&mut test_rng(1), n);
+        let ct = g.centroid_decomposition();
+
+        // size of each subtree of the centroid tree is at most half of its parent's
+        let mut size = vec![1; n];
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by_key(|&v| std::cmp::Reverse(ct.level(v)));
+        for &v in &order {
+            if let Some(p) = ct.parent(v) {
+                size[p] += size[v];
+            }
+        }
+        assert_eq!(size[ct.root()], n);
+        for v in 0..n {
+            if let Some(p) = ct.parent(v) {
+                assert!(size[v] * 2 <= size[p]);
+                assert_eq!(ct.level(v), ct.level(p) + 1);
+            }
+        }
+    }
+}
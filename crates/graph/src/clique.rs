@@ -0,0 +1,228 @@
+use super::UndirectedUnweightedListGraph;
+
+const MOD: [u64; 2] = [1_000_000_007, 998_244_353];
+
+/// Bron-Kerbosch with pivoting, `r` is the current clique
+fn bron_kerbosch(adj: &[u64], r: u64, mut p: u64, mut x: u64, best: &mut u64) {
+    if p == 0 {
+        if x == 0 && r.count_ones() > best.count_ones() {
+            *best = r;
+        }
+        return;
+    }
+    if (r | p).count_ones() <= best.count_ones() {
+        return;
+    }
+    // pivot with the most neighbors in p
+    let pivot = (0..64)
+        .filter(|&u| (p | x) >> u & 1 == 1)
+        .max_by_key(|&u| (p & adj[u]).count_ones())
+        .unwrap();
+    let mut cand = p & !adj[pivot];
+    while cand != 0 {
+        let v = cand.trailing_zeros() as usize;
+        cand &= cand - 1;
+        bron_kerbosch(adj, r | 1 << v, p & adj[v], x & adj[v], best);
+        p &= !(1 << v);
+        x |= 1 << v;
+    }
+}
+
+fn maximum_clique_of(adj: &[u64]) -> Vec<usize> {
+    let n = adj.len();
+    let all = if n == 64 { !0 } else { (1u64 << n) - 1 };
+    let mut best = 0;
+    bron_kerbosch(adj, 0, all, 0, &mut best);
+    (0..n).filter(|&v| best >> v & 1 == 1).collect()
+}
+
+impl UndirectedUnweightedListGraph {
+    /// adjacency as bit masks, self-loops are ignored
+    fn masks(&self) -> Vec<u64> {
+        assert!(self.len() <= 64, "too many vertices for u64 masks");
+        (0..self.len())
+            .map(|u| {
+                self.inner[u]
+                    .iter()
+                    .filter(|&&v| v != u)
+                    .fold(0, |m, &v| m | 1 << v)
+            })
+            .collect()
+    }
+
+    /// vertices of one of the largest cliques by Bron-Kerbosch with pivoting
+    ///
+    /// at most 64 vertices, fast enough for about 50
+    pub fn maximum_clique(&self) -> Vec<usize> {
+        maximum_clique_of(&self.masks())
+    }
+
+    /// vertices of one of the largest independent sets, i.e. a maximum clique of the complement
+    pub fn maximum_independent_set(&self) -> Vec<usize> {
+        let n = self.len();
+        let all = if n == 64 { !0 } else { (1u64 << n) - 1 };
+        let adj = self.masks();
+        let complement: Vec<u64> = (0..n).map(|u| all & !adj[u] & !(1 << u)).collect();
+        maximum_clique_of(&complement)
+    }
+
+    /// minimum number of colors so that adjacent vertices have different colors
+    ///
+    /// inclusion-exclusion over subsets in `O(2^n n)`, self-loops are ignored
+    ///
+    /// at most 24 vertices, it keeps three `u32` per subset (about 200MB for 24)
+    pub fn chromatic_number(&self) -> usize {
+        let n = self.len();
+        assert!(n <= 24, "too many vertices to enumerate subsets");
+        if n == 0 {
+            return 0;
+        }
+        let adj = self.masks();
+        // number of independent sets in each subset, including the empty one
+        let mut indep = vec![0u32; 1 << n];
+        indep[0] = 1;
+        for s in 1..1usize << n {
+            let v = s.trailing_zeros() as usize;
+            let rest = s & !(1 << v);
+            indep[s] = indep[rest] + indep[rest & !(adj[v] as usize)];
+        }
+        // indep[s]^k for each modulus
+        let mut pow = vec![vec![1u32; 1 << n]; MOD.len()];
+        for k in 1..n {
+            // number of ways to cover all vertices by k independent sets
+            let mut covered = false;
+            for (pow, &m) in pow.iter_mut().zip(MOD.iter()) {
+                let mut covers = 0;
+                for (s, p) in pow.iter_mut().enumerate() {
+                    let t = u64::from(*p) * u64::from(indep[s]) % m;
+                    *p = t as u32;
+                    if (n - s.count_ones() as usize) & 1 == 0 {
+                        covers = (covers + t) % m;
+                    } else {
+                        covers = (covers + m - t) % m;
+                    }
+                }
+                covered |= covers != 0;
+            }
+            if covered {
+                return k;
+            }
+        }
+        n
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::super::{random::test_rng, UndirectedUnweightedListGraph};
+
+    /// each pair is adjacent with probability `p`
+    fn random_graph<R: Rng>(rng: &mut R, n: usize, p: f64) -> UndirectedUnweightedListGraph {
+        let mut g = UndirectedUnweightedListGraph::new(n);
+        for u in 0..n {
+            for v in u + 1..n {
+                if rng.gen_bool(p) {
+                    g.add_edge(u, v);
+                }
+            }
+        }
+        g
+    }
+
+    fn is_clique(g: &UndirectedUnweightedListGraph, vs: &[usize]) -> bool {
+        vs.iter()
+            .all(|&u| vs.iter().all(|&v| u == v || g.neighbors(u).any(|w| w == v)))
+    }
+
+    /// try all colorings with k colors
+    fn colorable(g: &UndirectedUnweightedListGraph, k: usize) -> bool {
+        let n = g.len();
+        let mut color = vec![0; n];
+        loop {
+            if (0..g.edge_count()).all(|id| {
+                let (u, v) = g.edge(id);
+                color[u] != color[v]
+            }) {
+                return true;
+            }
+            let mut i = 0;
+            while i < n && color[i] == k - 1 {
+                color[i] = 0;
+                i += 1;
+            }
+            if i == n {
+                return false;
+            }
+            color[i] += 1;
+        }
+    }
+
+    #[test]
+    fn small_random() {
+        let mut rng = test_rng(3);
+        for n in 1..=9 {
+            let g = random_graph(&mut rng, n, 0.5);
+            let mut best = 0;
+            for s in 0..1usize << n {
+                let vs: Vec<usize> = (0..n).filter(|&v| s >> v & 1 == 1).collect();
+                if is_clique(&g, &vs) {
+                    best = best.max(vs.len());
+                }
+            }
+            let clique = g.maximum_clique();
+            assert!(is_clique(&g, &clique));
+            assert_eq!(clique.len(), best);
+
+            let indep = g.maximum_independent_set();
+            assert!(indep
+                .iter()
+                .all(|&u| indep.iter().all(|&v| !g.neighbors(u).any(|w| w == v))));
+
+            let k = g.chromatic_number();
+            assert!(colorable(&g, k));
+            assert!(k == 1 || !colorable(&g, k - 1));
+        }
+    }
+
+    #[test]
+    fn known_graphs() {
+        let cycle = |n: usize| {
+            let edges: Vec<_> = (0..n).map(|i| (i, (i + 1) % n)).collect();
+            UndirectedUnweightedListGraph::from_edges(n, &edges)
+        };
+        assert_eq!(cycle(5).chromatic_number(), 3);
+        assert_eq!(cycle(6).chromatic_number(), 2);
+        assert_eq!(cycle(7).maximum_independent_set().len(), 3);
+        assert_eq!(UndirectedUnweightedListGraph::new(3).chromatic_number(), 1);
+        assert_eq!(UndirectedUnweightedListGraph::new(0).chromatic_number(), 0);
+        let complete: Vec<_> = (0..12)
+            .flat_map(|u| (u + 1..12).map(move |v| (u, v)))
+            .collect();
+        assert_eq!(
+            UndirectedUnweightedListGraph::from_edges(12, &complete).chromatic_number(),
+            12
+        );
+
+        // Petersen graph
+        let mut edges = Vec::new();
+        for i in 0..5 {
+            edges.push((i, (i + 1) % 5));
+            edges.push((i, i + 5));
+            edges.push((i + 5, (i + 2) % 5 + 5));
+        }
+        let g = UndirectedUnweightedListGraph::from_edges(10, &edges);
+        assert_eq!(g.chromatic_number(), 3);
+        assert_eq!(g.maximum_clique().len(), 2);
+        assert_eq!(g.maximum_independent_set().len(), 4);
+    }
+
+    #[test]
+    fn fifty_vertices() {
+        let g = random_graph(&mut test_rng(8), 50, 0.7);
+        let clique = g.maximum_clique();
+        assert!(is_clique(&g, &clique));
+        assert!(clique.len() >= 8);
+    }
+}
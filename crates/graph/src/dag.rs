@@ -0,0 +1,220 @@
+use std::{cmp::Reverse, collections::BinaryHeap, ops::Add};
+
+use super::{DirectedUnweightedListGraph, DirectedWeightedListGraph, Weigh, Weighted};
+
+/// cycle found in a graph which was expected to be a DAG
+///
+/// `0[0] -> 0[1] -> ... -> 0[k - 1] -> 0[0]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle(pub Vec<usize>);
+
+/// reverse post order of iterative DFS, or a cycle
+fn topological_order<E, F: Fn(&E) -> usize>(inner: &[Vec<E>], to: F) -> Result<Vec<usize>, Cycle> {
+    let n = inner.len();
+    // 0: unvisited, 1: on stack, 2: finished
+    let mut state = vec![0u8; n];
+    let mut order = Vec::with_capacity(n);
+    let mut stack: Vec<(usize, usize)> = Vec::new();
+    for root in 0..n {
+        if state[root] != 0 {
+            continue;
+        }
+        state[root] = 1;
+        stack.push((root, 0));
+        while let Some((u, i)) = stack.last_mut() {
+            let u = *u;
+            if let Some(e) = inner[u].get(*i) {
+                *i += 1;
+                let v = to(e);
+                match state[v] {
+                    0 => {
+                        state[v] = 1;
+                        stack.push((v, 0));
+                    }
+                    1 => {
+                        let pos = stack.iter().position(|&(x, _)| x == v).unwrap();
+                        return Err(Cycle(stack[pos..].iter().map(|&(x, _)| x).collect()));
+                    }
+                    _ => {}
+                }
+            } else {
+                stack.pop();
+                state[u] = 2;
+                order.push(u);
+            }
+        }
+    }
+    order.reverse();
+    Ok(order)
+}
+
+impl DirectedUnweightedListGraph {
+    /// order where every edge goes forward
+    pub fn topological_sort(&self) -> Result<Vec<usize>, Cycle> {
+        topological_order(&self.inner, |&v| v)
+    }
+
+    /// lexicographically smallest topological order by Kahn's algorithm
+    pub fn lex_min_topological_sort(&self) -> Result<Vec<usize>, Cycle> {
+        let n = self.len();
+        let mut indeg = vec![0; n];
+        for &v in self.inner.iter().flatten() {
+            indeg[v] += 1;
+        }
+        let mut heap: BinaryHeap<_> = (0..n).filter(|&u| indeg[u] == 0).map(Reverse).collect();
+        let mut order = Vec::with_capacity(n);
+        while let Some(Reverse(u)) = heap.pop() {
+            order.push(u);
+            for &v in self.inner[u].iter() {
+                indeg[v] -= 1;
+                if indeg[v] == 0 {
+                    heap.push(Reverse(v));
+                }
+            }
+        }
+        if order.len() == n {
+            Ok(order)
+        } else {
+            Err(self.topological_sort().unwrap_err())
+        }
+    }
+
+    /// vertices of one of the paths which have the most edges
+    pub fn longest_path(&self) -> Result<Vec<usize>, Cycle> {
+        let order = self.topological_sort()?;
+        // longest path ending at each vertex
+        let mut len = vec![0usize; self.len()];
+        let mut prev = vec![None; self.len()];
+        for &u in order.iter() {
+            for &v in self.inner[u].iter() {
+                if len[v] < len[u] + 1 {
+                    len[v] = len[u] + 1;
+                    prev[v] = Some(u);
+                }
+            }
+        }
+        Ok(match (0..self.len()).rev().max_by_key(|&v| len[v]) {
+            Some(last) => restore(last, &prev),
+            None => Vec::new(),
+        })
+    }
+
+    /// number of paths from `from` to each vertex, modulo `modulo`
+    ///
+    /// any `modulo` up to `u64::MAX` works, sums are taken in `u128`
+    pub fn count_paths(&self, from: usize, modulo: u64) -> Result<Vec<u64>, Cycle> {
+        let order = self.topological_sort()?;
+        let mut count = vec![0; self.len()];
+        count[from] = 1 % modulo;
+        for &u in order.iter() {
+            for &v in self.inner[u].iter() {
+                count[v] = ((count[v] as u128 + count[u] as u128) % modulo as u128) as u64;
+            }
+        }
+        Ok(count)
+    }
+}
+
+impl<S> DirectedWeightedListGraph<S>
+where
+    Weighted<S>: Weigh<(usize, S)>,
+    S: Clone + Add<Output = S> + Ord + Default,
+{
+    /// order where every edge goes forward
+    pub fn topological_sort(&self) -> Result<Vec<usize>, Cycle> {
+        topological_order(&self.inner, |&(v, _)| v)
+    }
+
+    /// total weight and vertices of one of the heaviest paths
+    ///
+    /// `S::default()` is regarded as zero, so a path can be a single vertex
+    pub fn longest_path(&self) -> Result<(S, Vec<usize>), Cycle> {
+        let order = self.topological_sort()?;
+        let mut len = vec![S::default(); self.len()];
+        let mut prev = vec![None; self.len()];
+        for &u in order.iter() {
+            for (v, w) in self.inner[u].iter() {
+                let cand = len[u].clone() + w.clone();
+                if len[*v] < cand {
+                    len[*v] = cand;
+                    prev[*v] = Some(u);
+                }
+            }
+        }
+        Ok(
+            match (0..self.len()).rev().max_by_key(|&v| len[v].clone()) {
+                Some(last) => (len[last].clone(), restore(last, &prev)),
+                None => (S::default(), Vec::new()),
+            },
+        )
+    }
+}
+
+fn restore(last: usize, prev: &[Option<usize>]) -> Vec<usize> {
+    let mut path = vec![last];
+    while let Some(p) = prev[*path.last().unwrap()] {
+        path.push(p);
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{DirectedUnweightedListGraph, DirectedWeightedListGraph};
+    use super::Cycle;
+
+    #[test]
+    fn topological_sort() {
+        let edges = &[(3, 1), (1, 0), (2, 0), (3, 2), (4, 3)];
+        let g = DirectedUnweightedListGraph::from_edges(6, edges);
+
+        let order = g.topological_sort().unwrap();
+        let mut pos = [0; 6];
+        for (i, &v) in order.iter().enumerate() {
+            pos[v] = i;
+        }
+        assert!(edges.iter().all(|&(u, v)| pos[u] < pos[v]));
+
+        assert_eq!(g.lex_min_topological_sort(), Ok(vec![4, 3, 1, 2, 0, 5]));
+        let path = g.longest_path().unwrap();
+        assert_eq!(path.len(), 4);
+        assert!(path.windows(2).all(|w| edges.contains(&(w[0], w[1]))));
+        assert_eq!(g.count_paths(4, 1_000_000_007), Ok(vec![2, 1, 1, 1, 1, 0]));
+    }
+
+    #[test]
+    fn count_paths_large_modulo() {
+        // 70 diamonds in a row, 2^70 paths from the first vertex to the last
+        let mut edges = Vec::new();
+        for i in 0..70 {
+            let a = 3 * i;
+            edges.extend_from_slice(&[(a, a + 1), (a, a + 2), (a + 1, a + 3), (a + 2, a + 3)]);
+        }
+        let g = DirectedUnweightedListGraph::from_edges(211, &edges);
+        let modulo = u64::MAX - 58;
+        let expected = (1u128 << 70) % modulo as u128;
+        assert_eq!(g.count_paths(0, modulo).unwrap()[210] as u128, expected);
+    }
+
+    #[test]
+    fn cycle() {
+        let edges = &[(0, 1), (1, 2), (2, 3), (3, 1)];
+        let g = DirectedUnweightedListGraph::from_edges(4, edges);
+        assert_eq!(g.topological_sort(), Err(Cycle(vec![1, 2, 3])));
+        assert_eq!(g.lex_min_topological_sort(), Err(Cycle(vec![1, 2, 3])));
+
+        let g = DirectedUnweightedListGraph::from_edges(1, &[(0, 0)]);
+        assert_eq!(g.longest_path(), Err(Cycle(vec![0])));
+    }
+
+    #[test]
+    fn weighted_longest_path() {
+        let edges = &[(0, 1, 3), (1, 2, -5), (0, 2, 1), (2, 3, 4)];
+        let g = DirectedWeightedListGraph::from_edges(4, edges);
+        assert_eq!(g.longest_path(), Ok((5, vec![0, 2, 3])));
+
+        let g = DirectedWeightedListGraph::from_edges(2, &[(0, 1, -1i64)]);
+        assert_eq!(g.longest_path(), Ok((0, vec![0])));
+    }
+}
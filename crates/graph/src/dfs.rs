@@ -1,4 +1,7 @@
-use super::{Direct, UnweightedListGraph};
+use super::{
+    grid::{GridGraph, Pos},
+    Direct, UnweightedListGraph,
+};
 
 pub trait Dfsable<'a, V, N>: Sized {
     fn dfs(&'a self, start: N) -> Dfs<'a, Self, V, N>;
@@ -17,9 +20,8 @@ impl<'a, D: Direct> Iterator for Dfs<'a, UnweightedListGraph<D>, Vec<bool>, usiz
     type Item = Link<usize>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some((u, prev)) = self.stack.pop() {
-            let visited = self.visited.clone();
-            for &neighbor in self.graph.inner[u].iter().filter(|&&x| !visited[x]) {
+        while let Some((u, prev)) = self.stack.pop() {
+            for &neighbor in self.graph.inner[u].iter() {
                 if !self.visited[neighbor] {
                     self.visited[neighbor] = true;
                     self.stack.push((neighbor, Some(u)));
@@ -27,13 +29,10 @@ impl<'a, D: Direct> Iterator for Dfs<'a, UnweightedListGraph<D>, Vec<bool>, usiz
             }
 
             if let Some(prev) = prev {
-                Some((prev, u))
-            } else {
-                self.next()
+                return Some((prev, u));
             }
-        } else {
-            None
         }
+        None
     }
 }
 
@@ -68,10 +67,182 @@ impl<'a, D: Direct> Dfsable<'a, Vec<bool>, usize> for UnweightedListGraph<D> {
         }
     }
 }
+impl<'a, C> Iterator for Dfs<'a, GridGraph<C>, Vec<Vec<bool>>, Pos> {
+    type Item = Link<Pos>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((u, prev)) = self.stack.pop() {
+            for (r, c) in self.graph.neighbors(u) {
+                if !self.visited[r][c] {
+                    self.visited[r][c] = true;
+                    self.stack.push(((r, c), Some(u)));
+                }
+            }
+
+            if let Some(prev) = prev {
+                return Some((prev, u));
+            }
+        }
+        None
+    }
+}
+
+impl<'a, C> Dfs<'a, GridGraph<C>, Vec<Vec<bool>>, Pos> {
+    pub fn start(&self) -> Pos {
+        self.start
+    }
+}
+
+impl<'a, C> Dfsable<'a, Vec<Vec<bool>>, Pos> for GridGraph<C> {
+    fn dfs(&'a self, start: Pos) -> Dfs<'a, Self, Vec<Vec<bool>>, Pos> {
+        let mut visited = vec![vec![false; self.width()]; self.height()];
+        visited[start.0][start.1] = true;
+        Dfs {
+            graph: self,
+            visited,
+            start,
+            stack: vec![(start, None)],
+        }
+    }
+}
+
+/// event of depth first search, see `DfsEvents`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DfsEvent {
+    /// `u` is discovered
+    Enter(usize),
+    /// `v` is discovered from `u`, followed by `Enter(v)`
+    TreeEdge(usize, usize),
+    /// edge to an ancestor (including self loop)
+    BackEdge(usize, usize),
+    /// edge to an already finished vertex, only on directed graph
+    ForwardOrCross(usize, usize),
+    /// all edges from `u` are scanned
+    Exit(usize),
+}
+
+/// iterative DFS which reports every event with timestamps
+///
+/// on undirected graph, each edge id is scanned once, so the edge to the parent is skipped
+/// and every other non-tree edge, including a self loop, is reported once as `BackEdge`
+pub struct DfsEvents<'a, D: Direct> {
+    graph: &'a UnweightedListGraph<D>,
+    discovery: Vec<Option<usize>>,
+    finish: Vec<Option<usize>>,
+    parent: Vec<Option<usize>>,
+    time: usize,
+    /// (vertex, index of next neighbor)
+    stack: Vec<(usize, usize)>,
+    /// edges already scanned from either end, only on undirected graph
+    scanned: Vec<bool>,
+    roots: Vec<usize>,
+    pending: Option<DfsEvent>,
+}
+
+impl<'a, D: Direct> DfsEvents<'a, D> {
+    fn new(graph: &'a UnweightedListGraph<D>, mut roots: Vec<usize>) -> Self {
+        roots.reverse();
+        let n = graph.len();
+        Self {
+            graph,
+            discovery: vec![None; n],
+            finish: vec![None; n],
+            parent: vec![None; n],
+            time: 0,
+            stack: Vec::new(),
+            scanned: vec![false; if D::DIRECTED { 0 } else { graph.edge_count() }],
+            roots,
+            pending: None,
+        }
+    }
+
+    fn enter(&mut self, u: usize) {
+        self.discovery[u] = Some(self.time);
+        self.time += 1;
+        self.stack.push((u, 0));
+    }
+
+    /// time when each vertex is entered
+    pub fn discovery(&self) -> &[Option<usize>] {
+        &self.discovery
+    }
+
+    /// time when each vertex is exited
+    pub fn finish(&self) -> &[Option<usize>] {
+        &self.finish
+    }
+
+    /// parent on the DFS forest
+    pub fn parent(&self) -> &[Option<usize>] {
+        &self.parent
+    }
+}
+
+impl<'a, D: Direct> Iterator for DfsEvents<'a, D> {
+    type Item = DfsEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(e) = self.pending.take() {
+            return Some(e);
+        }
+        loop {
+            let (u, i) = match self.stack.last_mut() {
+                Some(top) => top,
+                None => {
+                    let root = self.roots.pop()?;
+                    if self.discovery[root].is_some() {
+                        continue;
+                    }
+                    self.enter(root);
+                    return Some(DfsEvent::Enter(root));
+                }
+            };
+            let u = *u;
+            if let Some(&v) = self.graph.inner[u].get(*i) {
+                let id = self.graph.ids[u][*i];
+                *i += 1;
+                if !D::DIRECTED {
+                    if self.scanned[id] {
+                        continue;
+                    }
+                    self.scanned[id] = true;
+                }
+                if self.discovery[v].is_none() {
+                    self.parent[v] = Some(u);
+                    self.enter(v);
+                    self.pending = Some(DfsEvent::Enter(v));
+                    return Some(DfsEvent::TreeEdge(u, v));
+                } else if self.finish[v].is_none() {
+                    return Some(DfsEvent::BackEdge(u, v));
+                } else if D::DIRECTED {
+                    return Some(DfsEvent::ForwardOrCross(u, v));
+                }
+            } else {
+                self.stack.pop();
+                self.finish[u] = Some(self.time);
+                self.time += 1;
+                return Some(DfsEvent::Exit(u));
+            }
+        }
+    }
+}
+
+impl<D: Direct> UnweightedListGraph<D> {
+    /// events of DFS from `start`
+    pub fn dfs_events(&self, start: usize) -> DfsEvents<'_, D> {
+        DfsEvents::new(self, vec![start])
+    }
+
+    /// events of DFS forest, roots are taken in increasing order
+    pub fn dfs_forest_events(&self) -> DfsEvents<'_, D> {
+        DfsEvents::new(self, (0..self.len()).collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::super::UndirectedUnweightedListGraph;
-    use super::Dfsable as _;
+    use super::super::{DirectedUnweightedListGraph, UndirectedUnweightedListGraph};
+    use super::{DfsEvent::*, Dfsable as _};
     #[test]
     fn dfs() {
         let edges = &[(0, 1), (1, 2), (1, 3), (3, 4)];
@@ -83,4 +254,73 @@ mod tests {
         assert_eq!(dfs.next(), Some((3, 4)));
         assert_eq!(dfs.next(), Some((1, 2)));
     }
+
+    #[test]
+    fn events_directed() {
+        let edges = &[(0, 1), (1, 2), (2, 0), (0, 2), (3, 2)];
+        let g = DirectedUnweightedListGraph::from_edges(4, edges);
+        let mut events = g.dfs_forest_events();
+        let all: Vec<_> = events.by_ref().collect();
+        assert_eq!(
+            all,
+            vec![
+                Enter(0),
+                TreeEdge(0, 1),
+                Enter(1),
+                TreeEdge(1, 2),
+                Enter(2),
+                BackEdge(2, 0),
+                Exit(2),
+                Exit(1),
+                ForwardOrCross(0, 2),
+                Exit(0),
+                Enter(3),
+                ForwardOrCross(3, 2),
+                Exit(3),
+            ]
+        );
+        assert_eq!(events.discovery(), &[Some(0), Some(1), Some(2), Some(6)]);
+        assert_eq!(events.finish(), &[Some(5), Some(4), Some(3), Some(7)]);
+        assert_eq!(events.parent(), &[None, Some(0), Some(1), None]);
+    }
+
+    #[test]
+    fn events_undirected() {
+        // 1 - 2 is a multi edge
+        let edges = &[(0, 1), (1, 2), (1, 2), (2, 0)];
+        let g = UndirectedUnweightedListGraph::from_edges(3, edges);
+        let back: Vec<_> = g
+            .dfs_events(0)
+            .filter(|e| matches!(e, BackEdge(..)))
+            .collect();
+        assert_eq!(back, vec![BackEdge(2, 1), BackEdge(2, 0)]);
+    }
+
+    #[test]
+    fn events_self_loop() {
+        let g = UndirectedUnweightedListGraph::from_edges(2, &[(0, 1), (1, 1), (0, 0)]);
+        let all: Vec<_> = g.dfs_events(0).collect();
+        assert_eq!(
+            all,
+            vec![
+                Enter(0),
+                TreeEdge(0, 1),
+                Enter(1),
+                BackEdge(1, 1),
+                Exit(1),
+                BackEdge(0, 0),
+                Exit(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn events_long_path() {
+        let n = 1_000_000;
+        let edges: Vec<_> = (0..n - 1).map(|i| (i, i + 1)).collect();
+        let g = DirectedUnweightedListGraph::from_edges(n, &edges);
+        let mut events = g.dfs_events(0);
+        assert_eq!(events.by_ref().count(), 3 * n - 1);
+        assert_eq!(events.finish()[0], Some(2 * n - 1));
+    }
 }
@@ -0,0 +1,148 @@
+use std::collections::VecDeque;
+
+use super::{
+    dijkstra::{is_shorter, ShortestPath},
+    Direct, WeightedListGraph,
+};
+
+impl<D: Direct> WeightedListGraph<usize, D> {
+    /// shortest path when every weight is 0 or 1, in `O(n + m)`
+    pub fn zero_one_bfs(&self, start: usize) -> ShortestPath<usize> {
+        let n = self.len();
+        let mut dist: Vec<Option<usize>> = vec![None; n];
+        let mut parent = vec![None; n];
+        let mut deque = VecDeque::new();
+        dist[start] = Some(0);
+        deque.push_back((0, start));
+        while let Some((d, u)) = deque.pop_front() {
+            if dist[u] != Some(d) {
+                continue;
+            }
+            for &(v, w) in self.inner[u].iter() {
+                assert!(w <= 1, "weight must be 0 or 1");
+                let cand = d + w;
+                if is_shorter(&cand, &dist[v]) {
+                    dist[v] = Some(cand);
+                    parent[v] = Some(u);
+                    if w == 0 {
+                        deque.push_front((cand, v));
+                    } else {
+                        deque.push_back((cand, v));
+                    }
+                }
+            }
+        }
+        ShortestPath::new(dist, parent)
+    }
+
+    /// Dial's algorithm, Dijkstra with buckets in `O(n * C + m)` where `C` is the maximum weight
+    pub fn dial(&self, start: usize) -> ShortestPath<usize> {
+        let n = self.len();
+        let c = self
+            .inner
+            .iter()
+            .flatten()
+            .map(|&(_, w)| w)
+            .max()
+            .unwrap_or(0);
+        // distances of queued vertices are in [d, d + c], so c + 1 buckets are enough
+        let mut buckets = vec![Vec::new(); c + 1];
+        let mut dist: Vec<Option<usize>> = vec![None; n];
+        let mut parent = vec![None; n];
+        let mut queued = 1;
+        dist[start] = Some(0);
+        buckets[0].push(start);
+        let mut d = 0;
+        while queued > 0 {
+            let bucket = std::mem::take(&mut buckets[d % (c + 1)]);
+            queued -= bucket.len();
+            for u in bucket {
+                if dist[u] != Some(d) {
+                    continue;
+                }
+                for &(v, w) in self.inner[u].iter() {
+                    let cand = d + w;
+                    if is_shorter(&cand, &dist[v]) {
+                        dist[v] = Some(cand);
+                        parent[v] = Some(u);
+                        if w == 0 {
+                            // processed in this round
+                            buckets[d % (c + 1)].push(v);
+                        } else {
+                            buckets[cand % (c + 1)].push(v);
+                        }
+                        queued += 1;
+                    }
+                }
+            }
+            if buckets[d % (c + 1)].is_empty() {
+                d += 1;
+            }
+        }
+        ShortestPath::new(dist, parent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::super::{random::test_rng, DirectedWeightedListGraph, UndirectedWeightedListGraph};
+
+    #[test]
+    fn zero_one_bfs() {
+        let edges = &[
+            (0, 1, 1),
+            (0, 2, 0),
+            (2, 1, 0),
+            (1, 3, 1),
+            (2, 3, 1),
+            (3, 4, 0),
+        ];
+        let g = DirectedWeightedListGraph::from_edges(6, edges);
+        let sp = g.zero_one_bfs(0);
+        assert_eq!(
+            sp.dist(),
+            &[Some(0), Some(0), Some(0), Some(1), Some(1), None]
+        );
+        assert_eq!(sp.path_to(1), Some(vec![0, 2, 1]));
+        assert_eq!(sp.dist(), g.dijkstra(0).dist());
+        assert_eq!(sp.dist(), g.dial(0).dist());
+    }
+
+    #[test]
+    fn same_as_dijkstra() {
+        let mut rng = test_rng(7);
+        for _ in 0..50 {
+            let n = rng.gen_range(1, 31);
+            let m = rng.gen_range(0, 120);
+            let edges: Vec<_> = (0..m)
+                .map(|_| {
+                    (
+                        rng.gen_range(0, n),
+                        rng.gen_range(0, n),
+                        rng.gen_range(0, 2usize),
+                    )
+                })
+                .collect();
+            let g = UndirectedWeightedListGraph::from_edges(n, &edges);
+            let expected = g.dijkstra(0);
+            assert_eq!(g.zero_one_bfs(0).dist(), expected.dist());
+            assert_eq!(g.dial(0).dist(), expected.dist());
+
+            let edges: Vec<_> = edges
+                .into_iter()
+                .map(|(u, v, _)| (u, v, rng.gen_range(0, 6)))
+                .collect();
+            let g = DirectedWeightedListGraph::from_edges(n, &edges);
+            let sp = g.dial(0);
+            assert_eq!(sp.dist(), g.dijkstra(0).dist());
+            for v in 0..n {
+                if let Some(path) = sp.path_to(v) {
+                    assert_eq!(path[0], 0);
+                    assert_eq!(*path.last().unwrap(), v);
+                }
+            }
+        }
+    }
+}
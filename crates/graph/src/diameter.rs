@@ -0,0 +1,111 @@
+use std::ops::Add;
+
+use super::{
+    bfs::Bfsable as _, UndirectedUnweightedListGraph, UndirectedWeightedListGraph, Weigh, Weighted,
+};
+
+fn farthest(dist: &[Option<usize>]) -> usize {
+    (0..dist.len()).max_by_key(|&v| dist[v]).unwrap()
+}
+
+impl UndirectedUnweightedListGraph {
+    /// number of edges of the longest path in the tree, and the path itself
+    pub fn diameter(&self) -> (usize, Vec<usize>) {
+        assert!(self.len() > 0, "empty tree");
+        let a = farthest(self.bfs(0).distances());
+        let mut bfs = self.bfs(a);
+        let b = farthest(bfs.distances());
+        let path = bfs.path_to(b).unwrap();
+        (path.len() - 1, path)
+    }
+
+    /// vertices minimizing the eccentricity, one or two vertices on the middle of the diameter
+    pub fn center(&self) -> Vec<usize> {
+        let (d, path) = self.diameter();
+        if d % 2 == 0 {
+            vec![path[d / 2]]
+        } else {
+            vec![path[d / 2], path[d / 2 + 1]]
+        }
+    }
+}
+
+impl<S> UndirectedWeightedListGraph<S>
+where
+    Weighted<S>: Weigh<(usize, S)>,
+    S: Clone + Add<Output = S> + Ord + Default,
+{
+    /// distance from `start` and parent for each vertex of the tree
+    fn tree_distances(&self, start: usize) -> (Vec<S>, Vec<Option<usize>>) {
+        let n = self.len();
+        let mut dist = vec![S::default(); n];
+        let mut parent = vec![None; n];
+        let mut stack = vec![start];
+        while let Some(u) = stack.pop() {
+            for (v, w) in self.inner[u].iter() {
+                if *v != start && parent[*v].is_none() {
+                    dist[*v] = dist[u].clone() + w.clone();
+                    parent[*v] = Some(u);
+                    stack.push(*v);
+                }
+            }
+        }
+        (dist, parent)
+    }
+
+    /// total weight of the longest path in the tree, and the path itself
+    ///
+    /// weights must be non-negative, `S::default()` is regarded as zero
+    pub fn diameter(&self) -> (S, Vec<usize>) {
+        assert!(self.len() > 0, "empty tree");
+        let (dist, _) = self.tree_distances(0);
+        let a = (0..self.len()).max_by_key(|&v| &dist[v]).unwrap();
+        let (dist, parent) = self.tree_distances(a);
+        let b = (0..self.len()).max_by_key(|&v| &dist[v]).unwrap();
+        let mut path = vec![b];
+        while let Some(p) = parent[*path.last().unwrap()] {
+            path.push(p);
+        }
+        (dist[b].clone(), path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{UndirectedUnweightedListGraph, UndirectedWeightedListGraph};
+
+    #[test]
+    fn unweighted() {
+        let g = UndirectedUnweightedListGraph::from_edges(
+            7,
+            &[(0, 1), (1, 2), (2, 3), (1, 4), (4, 5), (0, 6)],
+        );
+        let (d, path) = g.diameter();
+        assert_eq!(d, 4);
+        assert_eq!(path.len(), 5);
+        assert!(path[0] == 3 || path[0] == 5 || path[0] == 6);
+        assert_eq!(g.center(), vec![1]);
+
+        let g = UndirectedUnweightedListGraph::from_edges(4, &[(0, 1), (1, 2), (2, 3)]);
+        let mut c = g.center();
+        c.sort_unstable();
+        assert_eq!(c, vec![1, 2]);
+
+        let g = UndirectedUnweightedListGraph::from_edges(1, &[]);
+        assert_eq!(g.diameter(), (0, vec![0]));
+    }
+
+    #[test]
+    fn weighted() {
+        let g = UndirectedWeightedListGraph::from_edges(
+            5,
+            &[(0, 1, 3u64), (1, 2, 4), (1, 3, 10), (0, 4, 2)],
+        );
+        let (d, mut path) = g.diameter();
+        assert_eq!(d, 15);
+        if path[0] != 3 {
+            path.reverse();
+        }
+        assert_eq!(path, vec![3, 1, 0, 4]);
+    }
+}
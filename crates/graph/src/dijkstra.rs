@@ -0,0 +1,100 @@
+use std::{cmp::Reverse, collections::BinaryHeap, ops::Add};
+
+use super::{Direct, Weigh, Weighted, WeightedListGraph};
+
+/// distances and shortest path tree from a start
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPath<S> {
+    dist: Vec<Option<S>>,
+    parent: Vec<Option<usize>>,
+}
+
+impl<S> ShortestPath<S> {
+    pub(crate) fn new(dist: Vec<Option<S>>, parent: Vec<Option<usize>>) -> Self {
+        Self { dist, parent }
+    }
+
+    /// distance from the start, `None` if unreachable
+    pub fn dist(&self) -> &[Option<S>] {
+        &self.dist
+    }
+
+    /// previous vertex on a shortest path, `None` for the start and unreachable vertices
+    pub fn parent(&self) -> &[Option<usize>] {
+        &self.parent
+    }
+
+    /// vertices of a shortest path from the start to `goal`, both ends inclusive
+    pub fn path_to(&self, goal: usize) -> Option<Vec<usize>> {
+        self.dist[goal].as_ref()?;
+        let mut path = vec![goal];
+        while let Some(p) = self.parent[*path.last().unwrap()] {
+            path.push(p);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// whether `cand` is shorter than the current distance
+pub(crate) fn is_shorter<S: Ord>(cand: &S, current: &Option<S>) -> bool {
+    match current {
+        Some(current) => cand < current,
+        None => true,
+    }
+}
+
+impl<S, D> WeightedListGraph<S, D>
+where
+    Weighted<S>: Weigh<(usize, S)>,
+    S: Clone + Add<Output = S> + Ord + Default,
+    D: Direct,
+{
+    /// single source shortest path for non-negative weights in `O((n + m) log n)`
+    ///
+    /// `S::default()` is regarded as zero
+    pub fn dijkstra(&self, start: usize) -> ShortestPath<S> {
+        let n = self.len();
+        let mut dist: Vec<Option<S>> = vec![None; n];
+        let mut parent = vec![None; n];
+        let mut heap = BinaryHeap::new();
+        dist[start] = Some(S::default());
+        heap.push(Reverse((S::default(), start)));
+        while let Some(Reverse((d, u))) = heap.pop() {
+            if dist[u].as_ref() != Some(&d) {
+                continue;
+            }
+            for (v, w) in self.inner[u].iter() {
+                let cand = d.clone() + w.clone();
+                if is_shorter(&cand, &dist[*v]) {
+                    dist[*v] = Some(cand.clone());
+                    parent[*v] = Some(u);
+                    heap.push(Reverse((cand, *v)));
+                }
+            }
+        }
+        ShortestPath::new(dist, parent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{DirectedWeightedListGraph, UndirectedWeightedListGraph};
+
+    #[test]
+    fn dijkstra() {
+        let edges = &[(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 1), (2, 3, 5)];
+        let g = DirectedWeightedListGraph::from_edges(5, edges);
+        let sp = g.dijkstra(0);
+        assert_eq!(sp.dist(), &[Some(0), Some(3), Some(1), Some(4), None]);
+        assert_eq!(sp.path_to(3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(sp.path_to(4), None);
+        assert_eq!(g.dijkstra(3).dist()[0], None);
+
+        let g = UndirectedWeightedListGraph::from_edges(5, edges);
+        assert_eq!(
+            g.dijkstra(3).dist(),
+            &[Some(4), Some(1), Some(3), Some(0), None]
+        );
+    }
+}
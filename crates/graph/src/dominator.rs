@@ -0,0 +1,248 @@
+use ::bitset::BitSet;
+
+use super::DirectedUnweightedListGraph;
+
+const NIL: usize = usize::MAX;
+
+/// dominator tree of a flow graph from `root`
+///
+/// `d` dominates `v` if every path from the root to `v` passes `d`,
+/// unreachable vertices are not on the tree
+#[derive(Debug, Clone)]
+pub struct DominatorTree {
+    root: usize,
+    idom: Vec<Option<usize>>,
+    reachable: BitSet,
+    children: Vec<Vec<usize>>,
+    /// preorder on the dominator tree, for `dominates`
+    tin: Vec<usize>,
+    tout: Vec<usize>,
+}
+
+impl DominatorTree {
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// immediate dominator of each vertex, `None` for the root and unreachable vertices
+    pub fn idom(&self) -> &[Option<usize>] {
+        &self.idom
+    }
+
+    /// vertices reachable from the root
+    pub fn reachable(&self) -> &BitSet {
+        &self.reachable
+    }
+
+    /// vertices whose immediate dominator is `v`
+    pub fn children(&self, v: usize) -> &[usize] {
+        &self.children[v]
+    }
+
+    /// whether `a` dominates `b`, every reachable vertex dominates itself
+    pub fn dominates(&self, a: usize, b: usize) -> bool {
+        self.reachable[a]
+            && self.reachable[b]
+            && self.tin[a] <= self.tin[b]
+            && self.tin[b] < self.tout[a]
+    }
+
+    /// edges from immediate dominators to vertices
+    pub fn to_graph(&self) -> DirectedUnweightedListGraph {
+        let mut g = DirectedUnweightedListGraph::new(self.idom.len());
+        for (v, p) in self.idom.iter().enumerate() {
+            if let Some(p) = *p {
+                g.add_edge(p, v);
+            }
+        }
+        g
+    }
+}
+
+impl DirectedUnweightedListGraph {
+    /// dominator tree by Lengauer-Tarjan algorithm in `O(m log n)`
+    pub fn dominator_tree(&self, root: usize) -> DominatorTree {
+        let n = self.len();
+        // everything below is indexed by DFS preorder
+        let mut ord = vec![NIL; n];
+        let mut vertex = Vec::with_capacity(n);
+        let mut par = Vec::with_capacity(n);
+        let mut stack = vec![(root, NIL)];
+        while let Some((v, p)) = stack.pop() {
+            if ord[v] != NIL {
+                continue;
+            }
+            ord[v] = vertex.len();
+            vertex.push(v);
+            par.push(p);
+            for &u in self.inner[v].iter().rev() {
+                if ord[u] == NIL {
+                    stack.push((u, ord[v]));
+                }
+            }
+        }
+        let k = vertex.len();
+        let mut pred = vec![Vec::new(); k];
+        for &v in vertex.iter() {
+            for &u in self.inner[v].iter() {
+                pred[ord[u]].push(ord[v]);
+            }
+        }
+
+        let mut semi: Vec<usize> = (0..k).collect();
+        let mut idom = vec![0; k];
+        let mut ancestor = vec![NIL; k];
+        let mut label: Vec<usize> = (0..k).collect();
+        let mut bucket = vec![Vec::new(); k];
+        let mut path = Vec::new();
+        let mut eval =
+            |v: usize, ancestor: &mut Vec<usize>, label: &mut Vec<usize>, semi: &[usize]| {
+                if ancestor[v] == NIL {
+                    return v;
+                }
+                let mut u = v;
+                while ancestor[ancestor[u]] != NIL {
+                    path.push(u);
+                    u = ancestor[u];
+                }
+                while let Some(u) = path.pop() {
+                    let a = ancestor[u];
+                    if semi[label[a]] < semi[label[u]] {
+                        label[u] = label[a];
+                    }
+                    ancestor[u] = ancestor[a];
+                }
+                label[v]
+            };
+        for w in (1..k).rev() {
+            for &v in pred[w].iter() {
+                let u = eval(v, &mut ancestor, &mut label, &semi);
+                if semi[u] < semi[w] {
+                    semi[w] = semi[u];
+                }
+            }
+            bucket[semi[w]].push(w);
+            let p = par[w];
+            ancestor[w] = p;
+            for v in std::mem::take(&mut bucket[p]) {
+                let u = eval(v, &mut ancestor, &mut label, &semi);
+                idom[v] = if semi[u] < semi[v] { u } else { p };
+            }
+        }
+        for w in 1..k {
+            if idom[w] != semi[w] {
+                idom[w] = idom[idom[w]];
+            }
+        }
+
+        let mut res_idom = vec![None; n];
+        let mut children = vec![Vec::new(); n];
+        let mut reachable = BitSet::new(n);
+        reachable.entry(root);
+        for w in 1..k {
+            res_idom[vertex[w]] = Some(vertex[idom[w]]);
+            children[vertex[idom[w]]].push(vertex[w]);
+            reachable.entry(vertex[w]);
+        }
+
+        let mut tin = vec![0; n];
+        let mut tout = vec![0; n];
+        let mut timer = 0;
+        let mut stack = vec![(root, false)];
+        while let Some((v, done)) = stack.pop() {
+            if done {
+                tout[v] = timer;
+                continue;
+            }
+            tin[v] = timer;
+            timer += 1;
+            stack.push((v, true));
+            stack.extend(children[v].iter().map(|&c| (c, false)));
+        }
+
+        DominatorTree {
+            root,
+            idom: res_idom,
+            reachable,
+            children,
+            tin,
+            tout,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::super::{bfs::Bfsable as _, random::test_rng, DirectedUnweightedListGraph};
+
+    #[test]
+    fn small() {
+        // 0 -> 1 -> 2 -> 4, 0 -> 3 -> 4, 4 -> 5, 5 -> 1
+        let g = DirectedUnweightedListGraph::from_edges(
+            7,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 4),
+                (0, 3),
+                (3, 4),
+                (4, 5),
+                (5, 1),
+                (6, 0),
+            ],
+        );
+        let dt = g.dominator_tree(0);
+        assert_eq!(
+            dt.idom(),
+            &[None, Some(0), Some(1), Some(0), Some(0), Some(4), None]
+        );
+        assert!(dt.reachable()[5]);
+        assert!(!dt.reachable()[6]);
+        assert!(dt.dominates(0, 5));
+        assert!(dt.dominates(4, 5));
+        assert!(!dt.dominates(1, 5));
+        assert!(dt.dominates(2, 2));
+        assert!(!dt.dominates(6, 6));
+        assert_eq!(dt.to_graph().edge_count(), 5);
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = test_rng(11);
+        for _ in 0..50 {
+            let n = 8;
+            let edges: Vec<_> = (0..14)
+                .map(|_| (rng.gen_range(0, n), rng.gen_range(0, n)))
+                .collect();
+            let g = DirectedUnweightedListGraph::from_edges(n, &edges);
+            let dt = g.dominator_tree(0);
+
+            // d dominates v iff v is unreachable after removing d
+            let reach = |removed: usize| -> Vec<bool> {
+                let h = DirectedUnweightedListGraph::from_edges(
+                    n,
+                    &edges
+                        .iter()
+                        .copied()
+                        .filter(|&(a, b)| a != removed && b != removed)
+                        .collect::<Vec<_>>(),
+                );
+                h.bfs(0).distances().iter().map(|d| d.is_some()).collect()
+            };
+            let all = reach(n);
+            for d in 1..n {
+                let r = reach(d);
+                for v in 0..n {
+                    let expected = all[v] && all[d] && (v == d || !r[v]);
+                    assert_eq!(dt.dominates(d, v), expected);
+                }
+            }
+            for (v, &r) in all.iter().enumerate() {
+                assert_eq!(dt.reachable()[v], r);
+                assert_eq!(dt.dominates(0, v), r);
+            }
+        }
+    }
+}
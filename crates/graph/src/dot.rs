@@ -0,0 +1,211 @@
+use std::fmt::{Display, Write};
+
+use ::bitset::BitSet;
+
+use super::{Direct, ListGraph, UnweightedListGraph, Weigh, Weighted, WeightedListGraph};
+
+const HIGHLIGHT: &str = "color=red, penwidth=2";
+
+/// content of a quoted DOT string
+fn escape(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            res.push('\\');
+        }
+        res.push(c);
+    }
+    res
+}
+
+impl<S, D: Direct, W: Weigh<S>> ListGraph<S, D, W> {
+    /// ids of the edges along `path`, the first one if there are multi-edges
+    fn path_edges(&self, path: &[usize]) -> Vec<bool> {
+        let mut res = vec![false; self.ends.len()];
+        for w in path.windows(2) {
+            let (a, b) = (w[0], w[1]);
+            if let Some(&id) = self.ids[a].iter().find(|&&id| {
+                let (from, to) = self.ends[id];
+                (from, to) == (a, b) || (!D::DIRECTED && (from, to) == (b, a))
+            }) {
+                res[id] = true;
+            }
+        }
+        res
+    }
+
+    /// `label` is used only if the graph is weighted
+    fn write_dot<L: Fn(usize) -> String>(
+        &self,
+        label: L,
+        vertices: &[bool],
+        edges: &[bool],
+    ) -> String {
+        let (kind, arrow) = if D::DIRECTED {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let mut res = String::new();
+        writeln!(res, "{} {{", kind).unwrap();
+        for v in 0..self.inner.len() {
+            if vertices.get(v) == Some(&true) {
+                writeln!(res, "    {} [{}];", v, HIGHLIGHT).unwrap();
+            } else {
+                writeln!(res, "    {};", v).unwrap();
+            }
+        }
+        for (id, &(from, to)) in self.ends.iter().enumerate() {
+            let mut attrs = Vec::new();
+            if W::WEIGHTED {
+                attrs.push(format!("label=\"{}\"", escape(&label(id))));
+            }
+            if edges[id] {
+                attrs.push(HIGHLIGHT.to_string());
+            }
+            if attrs.is_empty() {
+                writeln!(res, "    {} {} {};", from, arrow, to).unwrap();
+            } else {
+                writeln!(res, "    {} {} {} [{}];", from, arrow, to, attrs.join(", ")).unwrap();
+            }
+        }
+        res.push_str("}\n");
+        res
+    }
+}
+
+impl<D: Direct> UnweightedListGraph<D> {
+    /// Graphviz DOT, vertices and edges are labeled by their indices
+    ///
+    /// ```ignore
+    /// std::fs::write("graph.dot", g.to_dot())?;
+    /// // $ dot -Tpng graph.dot -o graph.png
+    /// ```
+    pub fn to_dot(&self) -> String {
+        self.write_dot(|_| String::new(), &[], &vec![false; self.edge_count()])
+    }
+
+    /// DOT where vertices and edges along `path` are highlighted
+    pub fn to_dot_with_path(&self, path: &[usize]) -> String {
+        let mut vertices = vec![false; self.len()];
+        path.iter().for_each(|&v| vertices[v] = true);
+        self.write_dot(|_| String::new(), &vertices, &self.path_edges(path))
+    }
+
+    /// DOT where vertices in `set` are highlighted
+    pub fn to_dot_with_vertices(&self, set: &BitSet) -> String {
+        let vertices: Vec<bool> = (0..self.len()).map(|v| v < set.len() && set[v]).collect();
+        self.write_dot(
+            |_| String::new(),
+            &vertices,
+            &vec![false; self.edge_count()],
+        )
+    }
+}
+
+impl<S: Display, D: Direct> WeightedListGraph<S, D>
+where
+    Weighted<S>: Weigh<(usize, S)>,
+{
+    fn weight_labels(&self) -> Vec<String> {
+        let mut labels = vec![String::new(); self.edge_count()];
+        for (adj, ids) in self.inner.iter().zip(self.ids.iter()) {
+            for ((_, w), &id) in adj.iter().zip(ids.iter()) {
+                labels[id] = w.to_string();
+            }
+        }
+        labels
+    }
+
+    /// Graphviz DOT, edges are labeled by their weights
+    pub fn to_dot(&self) -> String {
+        let labels = self.weight_labels();
+        self.write_dot(
+            |id| labels[id].clone(),
+            &[],
+            &vec![false; self.edge_count()],
+        )
+    }
+
+    /// DOT where vertices and edges along `path` are highlighted
+    pub fn to_dot_with_path(&self, path: &[usize]) -> String {
+        let labels = self.weight_labels();
+        let mut vertices = vec![false; self.inner.len()];
+        path.iter().for_each(|&v| vertices[v] = true);
+        self.write_dot(|id| labels[id].clone(), &vertices, &self.path_edges(path))
+    }
+
+    /// DOT where vertices in `set` are highlighted
+    pub fn to_dot_with_vertices(&self, set: &BitSet) -> String {
+        let labels = self.weight_labels();
+        let vertices: Vec<bool> = (0..self.inner.len())
+            .map(|v| v < set.len() && set[v])
+            .collect();
+        self.write_dot(
+            |id| labels[id].clone(),
+            &vertices,
+            &vec![false; self.edge_count()],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt, ops::Add};
+
+    use ::bitset::BitSet;
+
+    use super::super::{
+        DirectedWeightedListGraph, UndirectedUnweightedListGraph, UndirectedWeightedListGraph,
+    };
+
+    #[test]
+    fn unweighted() {
+        let g = UndirectedUnweightedListGraph::from_edges(3, &[(0, 1), (1, 2), (2, 0)]);
+        assert_eq!(
+            g.to_dot(),
+            "graph {\n    0;\n    1;\n    2;\n    0 -- 1;\n    1 -- 2;\n    2 -- 0;\n}\n"
+        );
+        assert_eq!(
+            g.to_dot_with_path(&[0, 2]),
+            "graph {\n    0 [color=red, penwidth=2];\n    1;\n    2 [color=red, penwidth=2];\n    0 -- 1;\n    1 -- 2;\n    2 -- 0 [color=red, penwidth=2];\n}\n"
+        );
+        let set = BitSet::from(vec![false, true, false]);
+        assert!(g
+            .to_dot_with_vertices(&set)
+            .contains("    1 [color=red, penwidth=2];\n"));
+    }
+
+    #[test]
+    fn weighted() {
+        let g = DirectedWeightedListGraph::from_edges(2, &[(0, 1, 5), (1, 0, -3), (0, 1, 7)]);
+        assert_eq!(
+            g.to_dot_with_path(&[0, 1]),
+            "digraph {\n    0 [color=red, penwidth=2];\n    1 [color=red, penwidth=2];\n    0 -> 1 [label=\"5\", color=red, penwidth=2];\n    1 -> 0 [label=\"-3\"];\n    0 -> 1 [label=\"7\"];\n}\n"
+        );
+    }
+
+    #[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+    struct Name(&'static str);
+
+    impl Add for Name {
+        type Output = Name;
+        fn add(self, _: Name) -> Name {
+            self
+        }
+    }
+
+    impl fmt::Display for Name {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    #[test]
+    fn escaped_label() {
+        let g = UndirectedWeightedListGraph::from_edges(2, &[(0, 1, Name(r#"say "hi" \ bye"#))]);
+        assert!(g
+            .to_dot()
+            .contains(r#"    0 -- 1 [label="say \"hi\" \\ bye"];"#));
+    }
+}
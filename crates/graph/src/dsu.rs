@@ -0,0 +1,346 @@
+use std::mem::swap;
+
+use super::algebra::AbelianGroup;
+
+/// Disjoint Set Union with path compression and union by size
+#[derive(Debug, Clone)]
+pub struct Dsu {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl Dsu {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub fn leader(&mut self, a: usize) -> usize {
+        let mut root = a;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut v = a;
+        while self.parent[v] != root {
+            let next = self.parent[v];
+            self.parent[v] = root;
+            v = next;
+        }
+        root
+    }
+
+    /// returns the new leader
+    pub fn merge(&mut self, a: usize, b: usize) -> usize {
+        let mut x = self.leader(a);
+        let mut y = self.leader(b);
+        if x == y {
+            return x;
+        }
+        if self.size[x] < self.size[y] {
+            swap(&mut x, &mut y);
+        }
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        x
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.leader(a) == self.leader(b)
+    }
+
+    /// size of the group `a` belongs to
+    pub fn size(&mut self, a: usize) -> usize {
+        let x = self.leader(a);
+        self.size[x]
+    }
+
+    /// vertices of each group, ordered by the smallest vertex
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let n = self.len();
+        let mut index = vec![None; n];
+        let mut res: Vec<Vec<usize>> = Vec::new();
+        for v in 0..n {
+            let x = self.leader(v);
+            let i = *index[x].get_or_insert_with(|| {
+                res.push(Vec::new());
+                res.len() - 1
+            });
+            res[i].push(v);
+        }
+        res
+    }
+}
+
+/// Disjoint Set Union which can undo merges
+///
+/// no path compression, so each operation takes `O(log n)`
+#[derive(Debug, Clone)]
+pub struct RollbackDsu {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    /// `(absorbed leader, new leader)` or `None` for merges of the same group
+    history: Vec<Option<(usize, usize)>>,
+}
+
+impl RollbackDsu {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            history: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub fn leader(&self, mut a: usize) -> usize {
+        while self.parent[a] != a {
+            a = self.parent[a];
+        }
+        a
+    }
+
+    /// returns whether two different groups are merged
+    pub fn merge(&mut self, a: usize, b: usize) -> bool {
+        let mut x = self.leader(a);
+        let mut y = self.leader(b);
+        if x == y {
+            self.history.push(None);
+            return false;
+        }
+        if self.size[x] < self.size[y] {
+            swap(&mut x, &mut y);
+        }
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        self.history.push(Some((y, x)));
+        true
+    }
+
+    pub fn same(&self, a: usize, b: usize) -> bool {
+        self.leader(a) == self.leader(b)
+    }
+
+    pub fn size(&self, a: usize) -> usize {
+        self.size[self.leader(a)]
+    }
+
+    /// undo the last `merge`, returns false if there is nothing to undo
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(Some((y, x))) => {
+                self.parent[y] = y;
+                self.size[x] -= self.size[y];
+                true
+            }
+            Some(None) => true,
+            None => false,
+        }
+    }
+
+    /// current state, which can be restored by `rollback`
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// undo merges until the state returned by `snapshot`
+    pub fn rollback(&mut self, snapshot: usize) {
+        while self.history.len() > snapshot {
+            self.undo();
+        }
+    }
+}
+
+/// Disjoint Set Union which also manages differences of potentials between vertices
+///
+/// ```ignore
+/// let mut dsu = WeightedDsu::<Additive<i64>>::new(3);
+/// dsu.merge(0, 1, 5); // weight(1) - weight(0) == 5
+/// dsu.merge(1, 2, -2);
+/// assert_eq!(dsu.diff(0, 2), Some(3));
+/// ```
+pub struct WeightedDsu<G: AbelianGroup> {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    /// `weight(v) - weight(parent[v])`
+    potential: Vec<G::S>,
+}
+
+impl<G: AbelianGroup> WeightedDsu<G> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            potential: vec![G::identity(); n],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub fn leader(&mut self, a: usize) -> usize {
+        let mut path = Vec::new();
+        let mut root = a;
+        while self.parent[root] != root {
+            path.push(root);
+            root = self.parent[root];
+        }
+        // vertices nearer to the root are updated first
+        for &v in path.iter().rev() {
+            let p = self.parent[v];
+            if p != root {
+                self.potential[v] = G::binary_operation(&self.potential[v], &self.potential[p]);
+                self.parent[v] = root;
+            }
+        }
+        root
+    }
+
+    /// `weight(a) - weight(leader(a))`
+    pub fn weight(&mut self, a: usize) -> G::S {
+        self.leader(a);
+        self.potential[a].clone()
+    }
+
+    /// `weight(b) - weight(a)` if `a` and `b` are in the same group
+    pub fn diff(&mut self, a: usize, b: usize) -> Option<G::S> {
+        if self.leader(a) != self.leader(b) {
+            return None;
+        }
+        Some(G::binary_operation(
+            &self.potential[b],
+            &G::inverse(&self.potential[a]),
+        ))
+    }
+
+    /// add relation `weight(b) - weight(a) == w`
+    ///
+    /// returns false if it contradicts the relations already added
+    pub fn merge(&mut self, a: usize, b: usize, w: G::S) -> bool {
+        let x = self.leader(a);
+        let y = self.leader(b);
+        let wa = self.potential[a].clone();
+        let wb = self.potential[b].clone();
+        if x == y {
+            return G::binary_operation(&wb, &G::inverse(&wa)) == w;
+        }
+        // weight(y) - weight(x)
+        let d = G::binary_operation(&G::binary_operation(&w, &wa), &G::inverse(&wb));
+        if self.size[x] < self.size[y] {
+            self.parent[x] = y;
+            self.size[y] += self.size[x];
+            self.potential[x] = G::inverse(&d);
+        } else {
+            self.parent[y] = x;
+            self.size[x] += self.size[y];
+            self.potential[y] = d;
+        }
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.leader(a) == self.leader(b)
+    }
+
+    pub fn size(&mut self, a: usize) -> usize {
+        let x = self.leader(a);
+        self.size[x]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::algebra::{Additive, Xor};
+    use super::{Dsu, RollbackDsu, WeightedDsu};
+
+    #[test]
+    fn dsu() {
+        let mut dsu = Dsu::new(6);
+        dsu.merge(0, 3);
+        dsu.merge(4, 3);
+        dsu.merge(1, 5);
+        assert!(dsu.same(0, 4));
+        assert!(!dsu.same(0, 1));
+        assert_eq!(dsu.size(4), 3);
+        assert_eq!(dsu.groups(), vec![vec![0, 3, 4], vec![1, 5], vec![2]]);
+    }
+
+    #[test]
+    fn long_chain() {
+        let n = 1_000_000;
+        let mut dsu = Dsu::new(n);
+        let mut wdsu = WeightedDsu::<Additive<i64>>::new(n);
+        for i in 1..n {
+            dsu.merge(i - 1, i);
+            assert!(wdsu.merge(i - 1, i, 1));
+        }
+        assert_eq!(dsu.size(0), n);
+        assert_eq!(wdsu.diff(0, n - 1), Some(n as i64 - 1));
+    }
+
+    #[test]
+    fn rollback() {
+        let mut dsu = RollbackDsu::new(4);
+        assert!(dsu.merge(0, 1));
+        let snapshot = dsu.snapshot();
+        assert!(dsu.merge(1, 2));
+        assert!(!dsu.merge(0, 2));
+        assert!(dsu.merge(2, 3));
+        assert_eq!(dsu.size(0), 4);
+
+        assert!(dsu.undo());
+        assert!(!dsu.same(0, 3));
+        assert!(dsu.same(0, 2));
+        dsu.rollback(snapshot);
+        assert!(!dsu.same(0, 2));
+        assert!(dsu.same(0, 1));
+        assert_eq!(dsu.size(1), 2);
+        dsu.rollback(0);
+        assert!(!dsu.same(0, 1));
+        assert!(!dsu.undo());
+    }
+
+    #[test]
+    fn weighted() {
+        let mut dsu = WeightedDsu::<Additive<i64>>::new(5);
+        assert!(dsu.merge(0, 1, 5));
+        assert!(dsu.merge(2, 1, 2));
+        assert!(dsu.merge(3, 4, -1));
+        assert_eq!(dsu.diff(0, 2), Some(3));
+        assert_eq!(dsu.diff(2, 0), Some(-3));
+        assert_eq!(dsu.diff(0, 3), None);
+        assert!(dsu.merge(2, 4, 10));
+        assert_eq!(dsu.diff(0, 3), Some(14));
+        assert!(dsu.merge(0, 3, 14));
+        assert!(!dsu.merge(0, 3, 13));
+        let root = dsu.leader(4);
+        assert_eq!(dsu.weight(root), 0);
+
+        let mut dsu = WeightedDsu::<Xor<u32>>::new(3);
+        assert!(dsu.merge(0, 1, 0b101));
+        assert!(dsu.merge(1, 2, 0b011));
+        assert_eq!(dsu.diff(0, 2), Some(0b110));
+        assert!(!dsu.merge(2, 0, 0b111));
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use super::{dsu::RollbackDsu, UndirectedUnweightedListGraph};
+
+/// connectivity under edge insertions and deletions, answered offline
+///
+/// each edge lives on an interval of queries, which is put on a segment tree over time,
+/// then DFS on the segment tree with `RollbackDsu` answers all queries in `O(q log q log n)`
+///
+/// ```ignore
+/// let mut dc = OfflineDynamicConnectivity::new(3);
+/// dc.add_edge(0, 1);
+/// dc.same(0, 1);
+/// dc.remove_edge(0, 1);
+/// dc.same(0, 1);
+/// assert_eq!(dc.solve(), vec![true, false]);
+/// ```
+pub struct OfflineDynamicConnectivity {
+    n: usize,
+    queries: Vec<(usize, usize)>,
+    /// start times of edges currently alive, multi-edges are allowed
+    alive: HashMap<(usize, usize), Vec<usize>>,
+    /// `(from, to, u, v)`, edge `(u, v)` exists while answering queries in `from..to`
+    intervals: Vec<(usize, usize, usize, usize)>,
+}
+
+impl OfflineDynamicConnectivity {
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            queries: Vec::new(),
+            alive: HashMap::new(),
+            intervals: Vec::new(),
+        }
+    }
+
+    /// start with the edges of `g`
+    pub fn from_graph(g: &UndirectedUnweightedListGraph) -> Self {
+        let mut res = Self::new(g.len());
+        for id in 0..g.edge_count() {
+            let (u, v) = g.edge(id);
+            res.add_edge(u, v);
+        }
+        res
+    }
+
+    fn key(u: usize, v: usize) -> (usize, usize) {
+        (u.min(v), u.max(v))
+    }
+
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        assert!(u < self.n && v < self.n, "vertex out of range");
+        self.alive
+            .entry(Self::key(u, v))
+            .or_default()
+            .push(self.queries.len());
+    }
+
+    /// remove one of the edges `(u, v)` added before
+    pub fn remove_edge(&mut self, u: usize, v: usize) {
+        let key = Self::key(u, v);
+        let from = self
+            .alive
+            .get_mut(&key)
+            .and_then(|s| s.pop())
+            .expect("no such edge");
+        self.intervals
+            .push((from, self.queries.len(), key.0, key.1));
+    }
+
+    /// ask whether `u` and `v` are connected at this point, answered by `solve`
+    pub fn same(&mut self, u: usize, v: usize) {
+        assert!(u < self.n && v < self.n, "vertex out of range");
+        self.queries.push((u, v));
+    }
+
+    /// answers of `same` in the order of calls
+    pub fn solve(&self) -> Vec<bool> {
+        let q = self.queries.len();
+        let mut res = vec![false; q];
+        if q == 0 {
+            return res;
+        }
+        let size = q.next_power_of_two();
+        let mut seg = vec![Vec::new(); 2 * size];
+        let still_alive = self
+            .alive
+            .iter()
+            .flat_map(|(&(u, v), starts)| starts.iter().map(move |&from| (from, q, u, v)));
+        for (from, to, u, v) in self.intervals.iter().copied().chain(still_alive) {
+            let (mut l, mut r) = (from + size, to + size);
+            while l < r {
+                if l & 1 == 1 {
+                    seg[l].push((u, v));
+                    l += 1;
+                }
+                if r & 1 == 1 {
+                    r -= 1;
+                    seg[r].push((u, v));
+                }
+                l >>= 1;
+                r >>= 1;
+            }
+        }
+
+        let mut dsu = RollbackDsu::new(self.n);
+        // (node, snapshot before merging its edges), `None` when entering
+        let mut stack = vec![(1, None)];
+        while let Some((node, snapshot)) = stack.pop() {
+            if let Some(snapshot) = snapshot {
+                dsu.rollback(snapshot);
+                continue;
+            }
+            stack.push((node, Some(dsu.snapshot())));
+            for &(u, v) in seg[node].iter() {
+                dsu.merge(u, v);
+            }
+            if node >= size {
+                if let Some(&(u, v)) = self.queries.get(node - size) {
+                    res[node - size] = dsu.same(u, v);
+                }
+            } else {
+                stack.push((2 * node + 1, None));
+                stack.push((2 * node, None));
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::super::{dsu::Dsu, random::test_rng, UndirectedUnweightedListGraph};
+    use super::OfflineDynamicConnectivity;
+
+    #[test]
+    fn simple() {
+        let mut dc = OfflineDynamicConnectivity::new(4);
+        dc.add_edge(0, 1);
+        dc.add_edge(1, 2);
+        dc.same(0, 2);
+        dc.add_edge(2, 1);
+        dc.remove_edge(1, 2);
+        dc.same(0, 2);
+        dc.remove_edge(1, 2);
+        dc.same(0, 2);
+        dc.same(3, 3);
+        assert_eq!(dc.solve(), vec![true, true, false, true]);
+        assert!(OfflineDynamicConnectivity::new(2).solve().is_empty());
+
+        let g = UndirectedUnweightedListGraph::from_edges(3, &[(0, 1), (1, 2)]);
+        let mut dc = OfflineDynamicConnectivity::from_graph(&g);
+        dc.same(0, 2);
+        dc.remove_edge(2, 1);
+        dc.same(0, 2);
+        assert_eq!(dc.solve(), vec![true, false]);
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = test_rng(17);
+        let n = 10;
+        let mut dc = OfflineDynamicConnectivity::new(n);
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        let mut expected = Vec::new();
+        for _ in 0..2000 {
+            match rng.gen_range(0, 3) {
+                0 => {
+                    let e = (rng.gen_range(0, n), rng.gen_range(0, n));
+                    dc.add_edge(e.0, e.1);
+                    edges.push(e);
+                }
+                1 if !edges.is_empty() => {
+                    let (u, v) = edges.swap_remove(rng.gen_range(0, edges.len()));
+                    // the reversed pair is the same edge
+                    if rng.gen() {
+                        dc.remove_edge(u, v);
+                    } else {
+                        dc.remove_edge(v, u);
+                    }
+                }
+                _ => {
+                    let (u, v) = (rng.gen_range(0, n), rng.gen_range(0, n));
+                    dc.same(u, v);
+                    let mut dsu = Dsu::new(n);
+                    for &(a, b) in edges.iter() {
+                        dsu.merge(a, b);
+                    }
+                    expected.push(dsu.same(u, v));
+                }
+            }
+        }
+        assert_eq!(dc.solve(), expected);
+    }
+}
@@ -0,0 +1,180 @@
+use super::{Direct, ListGraph, Weigh};
+
+/// walk which uses every edge exactly once
+///
+/// `vertices[i] -> vertices[i + 1]` by the edge `edges[i]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trail {
+    pub vertices: Vec<usize>,
+    pub edges: Vec<usize>,
+}
+
+/// reason why an Eulerian trail does not exist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotEulerian {
+    /// vertices with odd degree (undirected) or in-degree different from out-degree (directed)
+    Unbalanced(Vec<usize>),
+    /// edges are not in one connected component
+    Disconnected,
+}
+
+impl<S, D: Direct, W: Weigh<S>> ListGraph<S, D, W> {
+    /// Eulerian trail which starts and ends at the same vertex, in `O(n + m)`
+    pub fn euler_circuit(&self) -> Result<Trail, NotEulerian> {
+        let unbalanced: Vec<usize> = self
+            .degree_diff()
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, d)| d != 0)
+            .map(|(v, _)| v)
+            .collect();
+        if !unbalanced.is_empty() {
+            return Err(NotEulerian::Unbalanced(unbalanced));
+        }
+        self.hierholzer(self.first_vertex_with_edge())
+    }
+
+    /// Eulerian trail, which is a circuit if possible, in `O(n + m)`
+    pub fn euler_path(&self) -> Result<Trail, NotEulerian> {
+        let diff = self.degree_diff();
+        let unbalanced: Vec<usize> = (0..diff.len()).filter(|&v| diff[v] != 0).collect();
+        let start = match unbalanced.len() {
+            0 => self.first_vertex_with_edge(),
+            2 if !D::DIRECTED => unbalanced[0],
+            2 if diff[unbalanced[0]] == 1 && diff[unbalanced[1]] == -1 => unbalanced[0],
+            2 if diff[unbalanced[1]] == 1 && diff[unbalanced[0]] == -1 => unbalanced[1],
+            _ => return Err(NotEulerian::Unbalanced(unbalanced)),
+        };
+        self.hierholzer(start)
+    }
+
+    /// out-degree minus in-degree if directed, parity of degree if undirected
+    fn degree_diff(&self) -> Vec<i64> {
+        let mut diff = vec![0i64; self.inner.len()];
+        for &(from, to) in self.ends.iter() {
+            if D::DIRECTED {
+                diff[from] += 1;
+                diff[to] -= 1;
+            } else {
+                diff[from] ^= 1;
+                diff[to] ^= 1;
+            }
+        }
+        diff
+    }
+
+    fn first_vertex_with_edge(&self) -> usize {
+        self.ends.first().map_or(0, |e| e.0)
+    }
+
+    fn hierholzer(&self, start: usize) -> Result<Trail, NotEulerian> {
+        let m = self.ends.len();
+        if self.inner.is_empty() {
+            return Ok(Trail {
+                vertices: Vec::new(),
+                edges: Vec::new(),
+            });
+        }
+        let mut used = vec![false; m];
+        let mut ptr = vec![0; self.inner.len()];
+        let mut vertices = Vec::with_capacity(m + 1);
+        let mut edges = Vec::with_capacity(m);
+        // (vertex, edge used to come to the vertex)
+        let mut stack = vec![(start, usize::MAX)];
+        while let Some(&(u, e)) = stack.last() {
+            let ids = &self.ids[u];
+            while ptr[u] < ids.len() && used[ids[ptr[u]]] {
+                ptr[u] += 1;
+            }
+            if let Some(&id) = ids.get(ptr[u]) {
+                used[id] = true;
+                let (from, to) = self.ends[id];
+                stack.push((if from == u { to } else { from }, id));
+            } else {
+                stack.pop();
+                vertices.push(u);
+                if e != usize::MAX {
+                    edges.push(e);
+                }
+            }
+        }
+        if edges.len() != m {
+            return Err(NotEulerian::Disconnected);
+        }
+        vertices.reverse();
+        edges.reverse();
+        Ok(Trail { vertices, edges })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{DirectedUnweightedListGraph, UndirectedUnweightedListGraph};
+    use super::{NotEulerian, Trail};
+
+    fn check<F: Fn(usize) -> (usize, usize)>(t: &Trail, m: usize, edge: F, directed: bool) {
+        assert_eq!(t.edges.len(), m);
+        assert_eq!(t.vertices.len(), m + 1);
+        let mut ids = t.edges.clone();
+        ids.sort_unstable();
+        assert!(ids.iter().enumerate().all(|(i, &e)| i == e));
+        for (i, &e) in t.edges.iter().enumerate() {
+            let (a, b) = edge(e);
+            let (u, v) = (t.vertices[i], t.vertices[i + 1]);
+            assert!((a, b) == (u, v) || (!directed && (a, b) == (v, u)));
+        }
+    }
+
+    #[test]
+    fn directed() {
+        let edges = [
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (0, 3),
+            (3, 0),
+            (1, 1),
+            (0, 1),
+            (1, 0),
+        ];
+        let g = DirectedUnweightedListGraph::from_edges(4, &edges);
+        let t = g.euler_circuit().unwrap();
+        check(&t, edges.len(), |e| g.edge(e), true);
+        assert_eq!(t.vertices[0], t.vertices[edges.len()]);
+
+        let g = DirectedUnweightedListGraph::from_edges(4, &[(0, 1), (1, 2), (2, 0), (2, 3)]);
+        assert_eq!(g.euler_circuit(), Err(NotEulerian::Unbalanced(vec![2, 3])));
+        let t = g.euler_path().unwrap();
+        check(&t, 4, |e| g.edge(e), true);
+        assert_eq!((t.vertices[0], t.vertices[4]), (2, 3));
+
+        let g = DirectedUnweightedListGraph::from_edges(4, &[(0, 1), (1, 0), (2, 3), (3, 2)]);
+        assert_eq!(g.euler_path(), Err(NotEulerian::Disconnected));
+        let g = DirectedUnweightedListGraph::from_edges(3, &[(0, 1), (0, 2)]);
+        assert_eq!(g.euler_path(), Err(NotEulerian::Unbalanced(vec![0, 1, 2])));
+    }
+
+    #[test]
+    fn undirected() {
+        // multi-edges and a self-loop
+        let edges = [(0, 1), (0, 1), (1, 2), (2, 3), (3, 1), (2, 2), (3, 4)];
+        let g = UndirectedUnweightedListGraph::from_edges(5, &edges);
+        assert_eq!(g.euler_circuit(), Err(NotEulerian::Unbalanced(vec![3, 4])));
+        let t = g.euler_path().unwrap();
+        check(&t, edges.len(), |e| g.edge(e), false);
+        let ends = (t.vertices[0], t.vertices[edges.len()]);
+        assert!(ends == (3, 4) || ends == (4, 3));
+
+        let g = UndirectedUnweightedListGraph::from_edges(3, &[]);
+        let t = g.euler_circuit().unwrap();
+        assert_eq!((t.vertices, t.edges), (vec![0], vec![]));
+    }
+
+    #[test]
+    fn large() {
+        let n = 1_000_000;
+        let edges: Vec<_> = (0..n).map(|i| (i, (i + 1) % n)).collect();
+        let g = DirectedUnweightedListGraph::from_edges(n, &edges);
+        assert_eq!(g.euler_circuit().unwrap().edges.len(), n);
+    }
+}
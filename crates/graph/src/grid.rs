@@ -0,0 +1,272 @@
+use std::collections::VecDeque;
+
+/// position on grid, `(row, col)`
+pub type Pos = (usize, usize);
+
+const DIR4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+const DIR8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// which cells are adjacent to a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// up, right, down, left
+    Four,
+    /// `Four` and diagonals
+    Eight,
+}
+
+impl Neighborhood {
+    fn directions(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighborhood::Four => &DIR4,
+            Neighborhood::Eight => &DIR8,
+        }
+    }
+}
+
+/// 2D grid whose cells are vertices
+///
+/// cells for which `wall` returns true are never entered
+pub struct GridGraph<C> {
+    inner: Vec<Vec<C>>,
+    height: usize,
+    width: usize,
+    wall: Box<dyn Fn(&C) -> bool>,
+    neighborhood: Neighborhood,
+}
+
+impl GridGraph<char> {
+    /// parse each line as a row of chars
+    ///
+    /// ```ignore
+    /// let g = GridGraph::from_lines(&["S.#", "..G"], |&c| c == '#');
+    /// ```
+    pub fn from_lines<S, F>(lines: &[S], wall: F) -> Self
+    where
+        S: AsRef<str>,
+        F: Fn(&char) -> bool + 'static,
+    {
+        Self::new(
+            lines.iter().map(|l| l.as_ref().chars().collect()).collect(),
+            wall,
+        )
+    }
+}
+
+impl<C> GridGraph<C> {
+    /// create grid with 4-neighborhood
+    ///
+    /// all rows must have the same length
+    pub fn new<F: Fn(&C) -> bool + 'static>(inner: Vec<Vec<C>>, wall: F) -> Self {
+        let height = inner.len();
+        let width = inner.first().map_or(0, |r| r.len());
+        assert!(
+            inner.iter().all(|r| r.len() == width),
+            "all rows must have the same length"
+        );
+        Self {
+            inner,
+            height,
+            width,
+            wall: Box::new(wall),
+            neighborhood: Neighborhood::Four,
+        }
+    }
+
+    pub fn with_neighborhood(mut self, neighborhood: Neighborhood) -> Self {
+        self.neighborhood = neighborhood;
+        self
+    }
+
+    pub fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// number of cells
+    pub fn len(&self) -> usize {
+        self.height * self.width
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, (r, c): Pos) -> &C {
+        &self.inner[r][c]
+    }
+
+    pub fn is_wall(&self, p: Pos) -> bool {
+        (self.wall)(self.get(p))
+    }
+
+    /// first cell (in row-major order) which satisfies `f`
+    pub fn position<F: Fn(&C) -> bool>(&self, f: F) -> Option<Pos> {
+        (0..self.height)
+            .flat_map(|r| (0..self.width).map(move |c| (r, c)))
+            .find(|&p| f(self.get(p)))
+    }
+
+    /// convert `(row, col)` into `row * width + col`
+    pub fn index(&self, (r, c): Pos) -> usize {
+        r * self.width + c
+    }
+
+    /// inverse of `index`
+    pub fn pos(&self, i: usize) -> Pos {
+        (i / self.width, i % self.width)
+    }
+
+    /// adjacent cells of `p` which are inside the grid and not walls
+    pub fn neighbors(&self, (r, c): Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighborhood
+            .directions()
+            .iter()
+            .filter_map(move |&(dr, dc)| {
+                let nr = r as isize + dr;
+                let nc = c as isize + dc;
+                if 0 <= nr && nr < self.height as isize && 0 <= nc && nc < self.width as isize {
+                    Some((nr as usize, nc as usize))
+                } else {
+                    None
+                }
+            })
+            .filter(move |&p| !self.is_wall(p))
+    }
+
+    /// multi-source BFS, returns distance from nearest start for each cell
+    pub fn distance_grid(&self, starts: &[Pos]) -> Vec<Vec<Option<usize>>> {
+        let mut distance = vec![vec![None; self.width]; self.height];
+        let mut queue = VecDeque::new();
+        for &(r, c) in starts {
+            if distance[r][c].is_none() {
+                distance[r][c] = Some(0);
+                queue.push_back((r, c));
+            }
+        }
+        while let Some(u) = queue.pop_front() {
+            let d = distance[u.0][u.1].unwrap();
+            for (nr, nc) in self.neighbors(u) {
+                if distance[nr][nc].is_none() {
+                    distance[nr][nc] = Some(d + 1);
+                    queue.push_back((nr, nc));
+                }
+            }
+        }
+        distance
+    }
+
+    /// multi-source 0-1 BFS
+    ///
+    /// entering cell `p` costs `cost(p)`, which must be 0 or 1
+    pub fn zero_one_bfs<F: Fn(&C) -> usize>(
+        &self,
+        starts: &[Pos],
+        cost: F,
+    ) -> Vec<Vec<Option<usize>>> {
+        let mut distance: Vec<Vec<Option<usize>>> = vec![vec![None; self.width]; self.height];
+        let mut deque = VecDeque::new();
+        for &(r, c) in starts {
+            distance[r][c] = Some(0);
+            deque.push_back((0, (r, c)));
+        }
+        while let Some((d, u)) = deque.pop_front() {
+            if distance[u.0][u.1] != Some(d) {
+                continue;
+            }
+            for (nr, nc) in self.neighbors(u) {
+                let w = cost(&self.inner[nr][nc]);
+                assert!(w <= 1, "cost must be 0 or 1");
+                let nd = d + w;
+                if let Some(x) = distance[nr][nc] {
+                    if x <= nd {
+                        continue;
+                    }
+                }
+                distance[nr][nc] = Some(nd);
+                if w == 0 {
+                    deque.push_front((nd, (nr, nc)));
+                } else {
+                    deque.push_back((nd, (nr, nc)));
+                }
+            }
+        }
+        distance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GridGraph, Neighborhood};
+    use crate::{bfs::Bfsable as _, dfs::Dfsable as _};
+
+    const MAZE: [&str; 4] = ["S.#.", ".##.", "...#", "#.G."];
+
+    #[test]
+    fn bfs_links() {
+        let g = GridGraph::from_lines(&MAZE, |&c| c == '#');
+        let s = g.position(|&c| c == 'S').unwrap();
+        let gl = g.position(|&c| c == 'G').unwrap();
+        assert_eq!(s, (0, 0));
+        assert_eq!(gl, (3, 2));
+
+        let mut bfs = g.bfs(s);
+        assert_eq!(bfs.next(), Some(((0, 0), (0, 1))));
+        assert_eq!(bfs.next(), Some(((0, 0), (1, 0))));
+        assert_eq!(g.bfs(s).dist(gl), Some(5));
+        assert_eq!(g.bfs(s).dist((0, 3)), None);
+        let path = g.bfs(s).path_to(gl).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!((path[0], path[5]), (s, gl));
+
+        let visited: Vec<_> = g.dfs(s).map(|(_, to)| to).collect();
+        assert_eq!(visited.len(), 8);
+        assert!(!visited.contains(&(0, 3)));
+    }
+
+    #[test]
+    fn eight_neighborhood() {
+        let g = GridGraph::from_lines(&MAZE, |&c| c == '#').with_neighborhood(Neighborhood::Eight);
+        let d = g.distance_grid(&[(0, 0)]);
+        assert_eq!(d[3][2], Some(3));
+        assert_eq!(d[0][3], Some(5));
+        assert_eq!(d[3][3], Some(4));
+    }
+
+    #[test]
+    fn multi_source_and_zero_one() {
+        let g = GridGraph::from_lines(&["....", "...."], |_| false);
+        let d = g.distance_grid(&[(0, 0), (1, 3)]);
+        assert_eq!(
+            d,
+            vec![
+                vec![Some(0), Some(1), Some(2), Some(1)],
+                vec![Some(1), Some(2), Some(1), Some(0)]
+            ]
+        );
+
+        // breaking a wall costs 1
+        let g = GridGraph::from_lines(&[".#.", "##.", "..."], |_| false);
+        let d = g.zero_one_bfs(&[(0, 0)], |&c| if c == '#' { 1 } else { 0 });
+        assert_eq!(d[0][2], Some(1));
+        assert_eq!(d[2][2], Some(1));
+        assert_eq!(d[1][1], Some(2));
+        assert_eq!(d[2][0], Some(1));
+    }
+}
@@ -0,0 +1,239 @@
+use std::{collections::VecDeque, mem::swap, ops::Range};
+
+use super::{ListGraph, Undirected, Weigh};
+
+/// Heavy-Light Decomposition
+///
+/// vertices are renumbered by `index` so that every heavy path and every subtree is contiguous,
+/// put values on an array in this order and use any range structure on it
+///
+/// for values on edges, put the value of edge `(parent(v), v)` at `index(v)` and use `path_edge_segments`
+pub struct Hld {
+    parent: Vec<Option<usize>>,
+    depth: Vec<usize>,
+    size: Vec<usize>,
+    head: Vec<usize>,
+    index: Vec<usize>,
+    order: Vec<usize>,
+}
+
+impl Hld {
+    /// `g` must be a tree
+    pub fn new<S, W: Weigh<S>>(g: &ListGraph<S, Undirected, W>, root: usize) -> Self {
+        let n = g.inner.len();
+        assert_eq!(g.edge_count() + 1, n, "not a tree");
+
+        let mut parent = vec![None; n];
+        let mut depth = vec![0; n];
+        let mut visited = vec![false; n];
+        let mut bfs = Vec::with_capacity(n);
+        let mut queue = VecDeque::new();
+        visited[root] = true;
+        queue.push_back(root);
+        while let Some(u) = queue.pop_front() {
+            bfs.push(u);
+            for v in g.neighbors(u) {
+                if !visited[v] {
+                    visited[v] = true;
+                    parent[v] = Some(u);
+                    depth[v] = depth[u] + 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+        assert_eq!(bfs.len(), n, "not connected");
+
+        let mut size = vec![1; n];
+        let mut heavy: Vec<Option<usize>> = vec![None; n];
+        for &v in bfs.iter().rev() {
+            if let Some(p) = parent[v] {
+                size[p] += size[v];
+                // no heavy child as large as `v` yet
+                if heavy[p].filter(|&h| size[h] >= size[v]).is_none() {
+                    heavy[p] = Some(v);
+                }
+            }
+        }
+
+        // preorder where the heavy child comes first
+        let mut head = vec![root; n];
+        let mut index = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            index[v] = order.len();
+            order.push(v);
+            for c in g.neighbors(v) {
+                if parent[c] == Some(v) && heavy[v] != Some(c) {
+                    head[c] = c;
+                    stack.push(c);
+                }
+            }
+            if let Some(h) = heavy[v] {
+                head[h] = head[v];
+                stack.push(h);
+            }
+        }
+
+        Self {
+            parent,
+            depth,
+            size,
+            head,
+            index,
+            order,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// position of `v` in the array
+    pub fn index(&self, v: usize) -> usize {
+        self.index[v]
+    }
+
+    /// vertices in order of `index`
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parent[v]
+    }
+
+    pub fn lca(&self, mut u: usize, mut v: usize) -> usize {
+        while self.head[u] != self.head[v] {
+            if self.index[self.head[u]] < self.index[self.head[v]] {
+                swap(&mut u, &mut v);
+            }
+            u = self.parent[self.head[u]].unwrap();
+        }
+        if self.depth[u] < self.depth[v] {
+            u
+        } else {
+            v
+        }
+    }
+
+    /// ranges of indices which cover the vertices on the path between `u` and `v`,
+    /// `O(log n)` ranges in no particular order
+    pub fn path_segments(&self, u: usize, v: usize) -> Vec<Range<usize>> {
+        self.segments(u, v, false)
+    }
+
+    /// same as `path_segments`, but the index of the lowest common ancestor is excluded,
+    /// so that the ranges cover the edges on the path
+    pub fn path_edge_segments(&self, u: usize, v: usize) -> Vec<Range<usize>> {
+        self.segments(u, v, true)
+    }
+
+    fn segments(&self, mut u: usize, mut v: usize, edge: bool) -> Vec<Range<usize>> {
+        let mut res = Vec::new();
+        while self.head[u] != self.head[v] {
+            if self.index[self.head[u]] < self.index[self.head[v]] {
+                swap(&mut u, &mut v);
+            }
+            res.push(self.index[self.head[u]]..self.index[u] + 1);
+            u = self.parent[self.head[u]].unwrap();
+        }
+        let (a, b) = if self.index[u] <= self.index[v] {
+            (self.index[u], self.index[v])
+        } else {
+            (self.index[v], self.index[u])
+        };
+        let a = if edge { a + 1 } else { a };
+        if a <= b {
+            res.push(a..b + 1);
+        }
+        res
+    }
+
+    /// range of indices of the subtree of `v`
+    pub fn subtree_range(&self, v: usize) -> Range<usize> {
+        self.index[v]..self.index[v] + self.size[v]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{tree::Tree, UndirectedUnweightedListGraph, UndirectedWeightedListGraph};
+    use super::Hld;
+
+    const EDGES: [(usize, usize); 9] = [
+        (0, 1),
+        (0, 2),
+        (1, 3),
+        (1, 4),
+        (4, 5),
+        (4, 6),
+        (2, 7),
+        (7, 8),
+        (8, 9),
+    ];
+
+    #[test]
+    fn path_segments() {
+        let g = UndirectedUnweightedListGraph::from_edges(10, &EDGES);
+        let hld = Hld::new(&g, 0);
+        let tree = Tree::new(&g, 0);
+        for u in 0..10 {
+            for v in 0..10 {
+                let mut covered: Vec<_> = hld
+                    .path_segments(u, v)
+                    .into_iter()
+                    .flatten()
+                    .map(|i| hld.order()[i])
+                    .collect();
+                covered.sort_unstable();
+                let mut path: Vec<_> = (0..=tree.distance(u, v))
+                    .map(|k| tree.jump(u, v, k).unwrap())
+                    .collect();
+                path.sort_unstable();
+                assert_eq!(covered, path);
+                assert_eq!(hld.lca(u, v), tree.lca(u, v));
+            }
+        }
+    }
+
+    #[test]
+    fn subtree_and_edges() {
+        let g = UndirectedWeightedListGraph::from_edges(
+            10,
+            &EDGES
+                .iter()
+                .map(|&(u, v)| (u, v, 1usize << v))
+                .collect::<Vec<_>>(),
+        );
+        let hld = Hld::new(&g, 0);
+
+        let mut sub: Vec<_> = hld.subtree_range(4).map(|i| hld.order()[i]).collect();
+        sub.sort_unstable();
+        assert_eq!(sub, vec![4, 5, 6]);
+        assert_eq!(hld.subtree_range(0), 0..10);
+
+        // weight of edge (parent(v), v) is put at index(v)
+        let mut a = [0; 10];
+        for v in 1..10 {
+            a[hld.index(v)] = 1usize << v;
+        }
+        let sum = |u, v| -> usize {
+            hld.path_edge_segments(u, v)
+                .into_iter()
+                .map(|r| a[r].iter().sum::<usize>())
+                .sum()
+        };
+        assert_eq!(sum(5, 3), (1 << 5) + (1 << 4) + (1 << 3));
+        assert_eq!(
+            sum(6, 9),
+            (1 << 6) + (1 << 4) + (1 << 1) + (1 << 2) + (1 << 7) + (1 << 8) + (1 << 9)
+        );
+        assert_eq!(sum(7, 7), 0);
+        assert!(hld.path_edge_segments(7, 7).is_empty());
+    }
+}
@@ -0,0 +1,487 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Write},
+    ops::Add,
+    str::FromStr,
+};
+
+use super::{
+    parse::{Base, ParseError},
+    Direct, UnweightedListGraph, Weigh, Weighted, WeightedListGraph,
+};
+
+/// subset of JSON enough for adjacency lists
+enum Json {
+    Bool(bool),
+    Number(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+/// adjacency lists need 4 levels, deeper input is rejected before the stack runs out
+const MAX_DEPTH: usize = 64;
+
+struct JsonParser<'a> {
+    s: &'a [u8],
+    pos: usize,
+    /// number of arrays and objects currently open
+    depth: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.s.len() && self.s[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self, expected: &'static str) -> Result<u8, ParseError> {
+        self.skip_whitespace();
+        self.s
+            .get(self.pos)
+            .copied()
+            .ok_or(ParseError::UnexpectedEof { expected })
+    }
+
+    fn expect(&mut self, c: u8, expected: &'static str) -> Result<(), ParseError> {
+        if self.peek(expected)? == c {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.invalid(expected))
+        }
+    }
+
+    fn invalid(&self, expected: &'static str) -> ParseError {
+        let end = (self.pos + 16).min(self.s.len());
+        ParseError::InvalidToken {
+            token: String::from_utf8_lossy(&self.s[self.pos..end]).into_owned(),
+            expected,
+        }
+    }
+
+    fn word(&mut self) -> &'a str {
+        let start = self.pos;
+        while self.pos < self.s.len() && !b",]}: \t\r\n".contains(&self.s[self.pos]) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.s[start..self.pos]).unwrap()
+    }
+
+    fn key(&mut self) -> Result<String, ParseError> {
+        self.expect(b'"', "key")?;
+        let start = self.pos;
+        while self.pos < self.s.len() && self.s[self.pos] != b'"' {
+            self.pos += 1;
+        }
+        let key = String::from_utf8_lossy(&self.s[start..self.pos]).into_owned();
+        self.expect(b'"', "end of key")?;
+        Ok(key)
+    }
+
+    /// `open` is already consumed, parse elements until `close`
+    fn elements<T, F>(&mut self, close: u8, mut f: F) -> Result<Vec<T>, ParseError>
+    where
+        F: FnMut(&mut Self) -> Result<T, ParseError>,
+    {
+        let mut res = Vec::new();
+        if self.peek("value")? == close {
+            self.pos += 1;
+            return Ok(res);
+        }
+        loop {
+            res.push(f(self)?);
+            match self.peek("',' or end of array or object")? {
+                b',' => self.pos += 1,
+                c if c == close => {
+                    self.pos += 1;
+                    return Ok(res);
+                }
+                _ => return Err(self.invalid("',' or end of array or object")),
+            }
+        }
+    }
+
+    /// consume `[` or `{`, failing if it is nested too deeply
+    fn open(&mut self) -> Result<(), ParseError> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.invalid("at most 64 nested arrays or objects"));
+        }
+        self.depth += 1;
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Json, ParseError> {
+        match self.peek("value")? {
+            b'[' => {
+                self.open()?;
+                let res = self.elements(b']', |p| p.value()).map(Json::Array);
+                self.depth -= 1;
+                res
+            }
+            b'{' => {
+                self.open()?;
+                let res = self
+                    .elements(b'}', |p| {
+                        let key = p.key()?;
+                        p.expect(b':', "':'")?;
+                        Ok((key, p.value()?))
+                    })
+                    .map(Json::Object);
+                self.depth -= 1;
+                res
+            }
+            _ => match self.word() {
+                "true" => Ok(Json::Bool(true)),
+                "false" => Ok(Json::Bool(false)),
+                "" => Err(self.invalid("value")),
+                w => Ok(Json::Number(w.to_string())),
+            },
+        }
+    }
+}
+
+fn invalid(token: &str, expected: &'static str) -> ParseError {
+    ParseError::InvalidToken {
+        token: token.to_string(),
+        expected,
+    }
+}
+
+fn describe(json: &Json) -> &'static str {
+    match json {
+        Json::Bool(_) => "bool",
+        Json::Number(_) => "number",
+        Json::Array(_) => "array",
+        Json::Object(_) => "object",
+    }
+}
+
+/// check `directed` and return each list of `adjacency`
+fn adjacency<D: Direct>(s: &str) -> Result<Vec<Vec<Json>>, ParseError> {
+    let mut parser = JsonParser {
+        s: s.as_bytes(),
+        pos: 0,
+        depth: 0,
+    };
+    let json = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos != parser.s.len() {
+        return Err(parser.invalid("end of input"));
+    }
+    let fields = match json {
+        Json::Object(fields) => fields,
+        other => return Err(invalid(describe(&other), "object")),
+    };
+    let mut directed = None;
+    let mut adjacency = None;
+    for (key, value) in fields {
+        match (key.as_str(), value) {
+            ("directed", Json::Bool(b)) => directed = Some(b),
+            ("adjacency", Json::Array(a)) => adjacency = Some(a),
+            ("directed", other) => return Err(invalid(describe(&other), "bool")),
+            ("adjacency", other) => return Err(invalid(describe(&other), "array")),
+            _ => {}
+        }
+    }
+    match directed {
+        Some(b) if b == D::DIRECTED => {}
+        Some(b) => {
+            return Err(invalid(
+                &b.to_string(),
+                if D::DIRECTED { "true" } else { "false" },
+            ))
+        }
+        None => {
+            return Err(ParseError::UnexpectedEof {
+                expected: "\"directed\"",
+            })
+        }
+    }
+    adjacency
+        .ok_or(ParseError::UnexpectedEof {
+            expected: "\"adjacency\"",
+        })?
+        .into_iter()
+        .map(|list| match list {
+            Json::Array(list) => Ok(list),
+            other => Err(invalid(describe(&other), "array")),
+        })
+        .collect()
+}
+
+fn number<T: FromStr>(json: &Json, expected: &'static str) -> Result<T, ParseError> {
+    match json {
+        Json::Number(w) => w.parse().map_err(|_| invalid(w, expected)),
+        other => Err(invalid(describe(other), expected)),
+    }
+}
+
+fn vertex(json: &Json, n: usize) -> Result<usize, ParseError> {
+    let v = number(json, "vertex")?;
+    if v >= n {
+        return Err(ParseError::VertexOutOfRange {
+            vertex: v,
+            n,
+            base: Base::Zero,
+        });
+    }
+    Ok(v)
+}
+
+/// whether the entry `u -> v` of the adjacency list is a new edge,
+/// undirected edges appear twice and are taken from the smaller end
+fn is_new_edge<D: Direct>(u: usize, v: usize, self_loops: &mut usize) -> bool {
+    if D::DIRECTED || u < v {
+        true
+    } else if u == v {
+        *self_loops += 1;
+        *self_loops % 2 == 1
+    } else {
+        false
+    }
+}
+
+/// entries of an undirected adjacency list, which must pair up as `u -> v` and `v -> u`
+struct Mirrors<W> {
+    count: BTreeMap<(usize, usize, W), i64>,
+}
+
+impl<W: Ord> Mirrors<W> {
+    fn new() -> Self {
+        Self {
+            count: BTreeMap::new(),
+        }
+    }
+
+    fn add(&mut self, u: usize, v: usize, w: W) {
+        let d = if u <= v { 1 } else { -1 };
+        *self.count.entry((u.min(v), u.max(v), w)).or_insert(0) += d;
+    }
+
+    /// self loops appear twice in the list of their vertex
+    fn check(&self) -> Result<(), ParseError> {
+        for (&(u, v, _), &c) in &self.count {
+            if (u < v && c != 0) || (u == v && c % 2 != 0) {
+                return Err(ParseError::Asymmetric {
+                    u: if c > 0 { u } else { v },
+                    v: if c > 0 { v } else { u },
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<D: Direct> UnweightedListGraph<D> {
+    /// `{"directed":false,"adjacency":[[1],[0]]}`
+    pub fn to_json(&self) -> String {
+        let mut res = format!("{{\"directed\":{},\"adjacency\":[", D::DIRECTED);
+        for (u, adj) in self.inner.iter().enumerate() {
+            if u > 0 {
+                res.push(',');
+            }
+            res.push('[');
+            for (i, v) in adj.iter().enumerate() {
+                if i > 0 {
+                    res.push(',');
+                }
+                write!(res, "{}", v).unwrap();
+            }
+            res.push(']');
+        }
+        res.push_str("]}");
+        res
+    }
+
+    /// inverse of `to_json`, edge ids and order of neighbors may differ from the original graph
+    ///
+    /// every undirected entry `u -> v` needs the mirrored entry `v -> u`, otherwise `ParseError::Asymmetric`
+    pub fn from_json(s: &str) -> Result<Self, ParseError> {
+        let adjacency = adjacency::<D>(s)?;
+        let n = adjacency.len();
+        let mut res = Self::new(n);
+        let mut mirrors = Mirrors::new();
+        for (u, list) in adjacency.iter().enumerate() {
+            let mut self_loops = 0;
+            for e in list {
+                let v = vertex(e, n)?;
+                if !D::DIRECTED {
+                    mirrors.add(u, v, ());
+                }
+                if is_new_edge::<D>(u, v, &mut self_loops) {
+                    res.inner[u].push(v);
+                    if !D::DIRECTED {
+                        res.inner[v].push(u);
+                    }
+                    res.push_edge(u, v);
+                }
+            }
+        }
+        mirrors.check()?;
+        Ok(res)
+    }
+}
+
+impl<S, D: Direct> WeightedListGraph<S, D>
+where
+    Weighted<S>: Weigh<(usize, S)>,
+    S: Clone + Add + Ord + Display + FromStr,
+{
+    /// `{"directed":true,"adjacency":[[[1,5]],[]]}`, each entry is `[to, weight]`
+    pub fn to_json(&self) -> String {
+        let mut res = format!("{{\"directed\":{},\"adjacency\":[", D::DIRECTED);
+        for (u, adj) in self.inner.iter().enumerate() {
+            if u > 0 {
+                res.push(',');
+            }
+            res.push('[');
+            for (i, (v, w)) in adj.iter().enumerate() {
+                if i > 0 {
+                    res.push(',');
+                }
+                write!(res, "[{},{}]", v, w).unwrap();
+            }
+            res.push(']');
+        }
+        res.push_str("]}");
+        res
+    }
+
+    /// inverse of `to_json`, edge ids and order of neighbors may differ from the original graph
+    ///
+    /// every undirected entry `u -> v` needs the mirrored entry `v -> u`, otherwise `ParseError::Asymmetric`
+    pub fn from_json(s: &str) -> Result<Self, ParseError> {
+        let adjacency = adjacency::<D>(s)?;
+        let n = adjacency.len();
+        let mut res = Self::new(n);
+        let mut mirrors = Mirrors::new();
+        for (u, list) in adjacency.iter().enumerate() {
+            let mut self_loops = 0;
+            for e in list {
+                let (v, w) = match e {
+                    Json::Array(pair) if pair.len() == 2 => {
+                        (vertex(&pair[0], n)?, number::<S>(&pair[1], "weight")?)
+                    }
+                    other => return Err(invalid(describe(other), "[to, weight]")),
+                };
+                if !D::DIRECTED {
+                    mirrors.add(u, v, w.clone());
+                }
+                if is_new_edge::<D>(u, v, &mut self_loops) {
+                    res.inner[u].push((v, w.clone()));
+                    if !D::DIRECTED {
+                        res.inner[v].push((u, w));
+                    }
+                    res.push_edge(u, v);
+                }
+            }
+        }
+        mirrors.check()?;
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        parse::ParseError, DirectedUnweightedListGraph, UndirectedUnweightedListGraph,
+        UndirectedWeightedListGraph,
+    };
+
+    #[test]
+    fn round_trip() {
+        let g = UndirectedUnweightedListGraph::from_edges(4, &[(0, 1), (1, 2), (2, 2), (1, 0)]);
+        let json = g.to_json();
+        assert_eq!(
+            json,
+            r#"{"directed":false,"adjacency":[[1,1],[0,2,0],[1,2,2],[]]}"#
+        );
+        let h = UndirectedUnweightedListGraph::from_json(&json).unwrap();
+        assert_eq!(h.edge_count(), 4);
+        for u in 0..4 {
+            let mut a: Vec<_> = g.neighbors(u).collect();
+            let mut b: Vec<_> = h.neighbors(u).collect();
+            a.sort_unstable();
+            b.sort_unstable();
+            assert_eq!(a, b);
+        }
+
+        let g = UndirectedWeightedListGraph::from_edges(3, &[(0, 1, -2i64), (2, 1, 10)]);
+        let json = g.to_json();
+        assert_eq!(
+            json,
+            r#"{"directed":false,"adjacency":[[[1,-2]],[[0,-2],[2,10]],[[1,10]]]}"#
+        );
+        let h = UndirectedWeightedListGraph::<i64>::from_json(&json).unwrap();
+        assert_eq!(h.weighted_edges(), vec![(0, 1, -2), (1, 2, 10)]);
+    }
+
+    #[test]
+    fn import() {
+        let g = DirectedUnweightedListGraph::from_json(
+            "{ \"adjacency\": [ [1, 2], [2], [] ],\n  \"directed\": true }",
+        )
+        .unwrap();
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(g.edge(1), (0, 2));
+
+        assert!(matches!(
+            DirectedUnweightedListGraph::from_json(r#"{"directed":false,"adjacency":[]}"#),
+            Err(ParseError::InvalidToken { .. })
+        ));
+        assert!(matches!(
+            DirectedUnweightedListGraph::from_json(r#"{"directed":true,"adjacency":[[3]]}"#),
+            Err(ParseError::VertexOutOfRange {
+                vertex: 3,
+                n: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            DirectedUnweightedListGraph::from_json(r#"{"directed":true,"adjacency":[[0]"#),
+            Err(ParseError::UnexpectedEof { .. })
+        ));
+    }
+
+    #[test]
+    fn deeply_nested() {
+        let s = format!(
+            "{{\"directed\":true,\"adjacency\":{}{}}}",
+            "[".repeat(1_000_000),
+            "]".repeat(1_000_000)
+        );
+        assert!(matches!(
+            DirectedUnweightedListGraph::from_json(&s),
+            Err(ParseError::InvalidToken { .. })
+        ));
+    }
+
+    #[test]
+    fn asymmetric() {
+        assert!(matches!(
+            UndirectedUnweightedListGraph::from_json(r#"{"directed":false,"adjacency":[[1],[]]}"#),
+            Err(ParseError::Asymmetric { u: 0, v: 1 })
+        ));
+        assert!(matches!(
+            UndirectedUnweightedListGraph::from_json(r#"{"directed":false,"adjacency":[[],[0]]}"#),
+            Err(ParseError::Asymmetric { u: 1, v: 0 })
+        ));
+        assert!(matches!(
+            UndirectedUnweightedListGraph::from_json(r#"{"directed":false,"adjacency":[[0]]}"#),
+            Err(ParseError::Asymmetric { u: 0, v: 0 })
+        ));
+        assert!(matches!(
+            UndirectedWeightedListGraph::<i64>::from_json(
+                r#"{"directed":false,"adjacency":[[[1,3]],[[0,4]]]}"#
+            ),
+            Err(ParseError::Asymmetric { .. })
+        ));
+        let g = UndirectedUnweightedListGraph::from_json(
+            r#"{"directed":false,"adjacency":[[1,0,0],[0]]}"#,
+        )
+        .unwrap();
+        assert_eq!(g.edge_count(), 2);
+    }
+}
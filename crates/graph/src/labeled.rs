@@ -0,0 +1,270 @@
+use std::{collections::HashMap, hash::Hash, ops::Add};
+
+use super::{
+    bfs::Bfsable as _, dsu::Dsu, Direct, DirectedUnweightedListGraph, DirectedWeightedListGraph,
+    ListGraph, Undirected, UndirectedUnweightedListGraph, UndirectedWeightedListGraph,
+    UnweightedListGraph, Weigh, Weighted, WeightedListGraph,
+};
+
+/// graph whose vertices are named by arbitrary keys
+///
+/// keys are mapped to dense indices `0..n` in order of appearance,
+/// so that every algorithm on `graph()` can be used and its output translated by `key`
+///
+/// ```
+/// use graph::{LabeledGraph, UndirectedUnweightedListGraph};
+///
+/// let mut g = LabeledGraph::<&str, UndirectedUnweightedListGraph>::new();
+/// g.add_edge("tokyo", "osaka");
+/// g.add_edge("osaka", "fukuoka");
+/// assert_eq!(g.bfs_path(&"tokyo", &"fukuoka"), Some(vec![&"tokyo", &"osaka", &"fukuoka"]));
+/// ```
+pub struct LabeledGraph<K, G> {
+    graph: G,
+    keys: Vec<K>,
+    index: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Clone, S, D: Direct, W: Weigh<S>> LabeledGraph<K, ListGraph<S, D, W>> {
+    pub fn graph(&self) -> &ListGraph<S, D, W> {
+        &self.graph
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// index of `key`, `None` if it has not appeared
+    pub fn index(&self, key: &K) -> Option<usize> {
+        self.index.get(key).copied()
+    }
+
+    /// key of the vertex `v`
+    pub fn key(&self, v: usize) -> &K {
+        &self.keys[v]
+    }
+
+    /// keys in order of indices
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// translate indices into keys
+    pub fn keys_of(&self, vertices: &[usize]) -> Vec<&K> {
+        vertices.iter().map(|&v| &self.keys[v]).collect()
+    }
+
+    /// index of `key`, which is added as an isolated vertex if it is new
+    pub fn add_vertex(&mut self, key: K) -> usize {
+        if let Some(&v) = self.index.get(&key) {
+            return v;
+        }
+        let v = self.graph.add_vertex();
+        self.keys.push(key.clone());
+        self.index.insert(key, v);
+        v
+    }
+}
+
+impl<K: Hash + Eq + Clone, D: Direct> LabeledGraph<K, UnweightedListGraph<D>> {
+    pub fn new() -> Self {
+        Self {
+            graph: UnweightedListGraph::new(0),
+            keys: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// links of BFS from `start`, `None` if `start` has not appeared
+    pub fn bfs_links(&self, start: &K) -> Option<Vec<(&K, &K)>> {
+        let s = self.index(start)?;
+        Some(
+            self.graph
+                .bfs(s)
+                .map(|(u, v)| (&self.keys[u], &self.keys[v]))
+                .collect(),
+        )
+    }
+
+    /// one of the shortest paths from `start` to `goal`, both ends inclusive
+    pub fn bfs_path(&self, start: &K, goal: &K) -> Option<Vec<&K>> {
+        let path = self
+            .graph
+            .bfs(self.index(start)?)
+            .path_to(self.index(goal)?)?;
+        Some(self.keys_of(&path))
+    }
+}
+
+impl<K: Hash + Eq + Clone, D: Direct> Default for LabeledGraph<K, UnweightedListGraph<D>> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone> LabeledGraph<K, UndirectedUnweightedListGraph> {
+    /// returns id of the edge, new keys are added as vertices
+    pub fn add_edge(&mut self, from: K, to: K) -> usize {
+        let u = self.add_vertex(from);
+        let v = self.add_vertex(to);
+        self.graph.add_edge(u, v)
+    }
+}
+
+impl<K: Hash + Eq + Clone> LabeledGraph<K, DirectedUnweightedListGraph> {
+    /// returns id of the edge, new keys are added as vertices
+    pub fn add_edge(&mut self, from: K, to: K) -> usize {
+        let u = self.add_vertex(from);
+        let v = self.add_vertex(to);
+        self.graph.add_edge(u, v)
+    }
+
+    /// strongly connected components, in topological order
+    pub fn scc(&self) -> Vec<Vec<&K>> {
+        self.graph
+            .scc()
+            .iter()
+            .map(|group| self.keys_of(group))
+            .collect()
+    }
+}
+
+impl<K: Hash + Eq + Clone, S, W: Weigh<S>> LabeledGraph<K, ListGraph<S, Undirected, W>> {
+    /// connected components, ordered by their smallest indices
+    pub fn components(&self) -> Vec<Vec<&K>> {
+        let mut dsu = Dsu::new(self.len());
+        for id in 0..self.graph.edge_count() {
+            let (u, v) = self.graph.edge(id);
+            dsu.merge(u, v);
+        }
+        dsu.groups()
+            .iter()
+            .map(|group| self.keys_of(group))
+            .collect()
+    }
+}
+
+impl<K, S, D> LabeledGraph<K, WeightedListGraph<S, D>>
+where
+    K: Hash + Eq + Clone,
+    Weighted<S>: Weigh<(usize, S)>,
+    S: Clone + Add<Output = S> + Ord + Default,
+    D: Direct,
+{
+    pub fn new() -> Self {
+        Self {
+            graph: WeightedListGraph::new(0),
+            keys: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// length and vertices of one of the shortest paths by Dijkstra
+    pub fn dijkstra_path(&self, start: &K, goal: &K) -> Option<(S, Vec<&K>)> {
+        let sp = self.graph.dijkstra(self.index(start)?);
+        let goal = self.index(goal)?;
+        let path = sp.path_to(goal)?;
+        Some((sp.dist()[goal].clone()?, self.keys_of(&path)))
+    }
+}
+
+impl<K, S, D> Default for LabeledGraph<K, WeightedListGraph<S, D>>
+where
+    K: Hash + Eq + Clone,
+    Weighted<S>: Weigh<(usize, S)>,
+    S: Clone + Add<Output = S> + Ord + Default,
+    D: Direct,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, S> LabeledGraph<K, UndirectedWeightedListGraph<S>>
+where
+    K: Hash + Eq + Clone,
+    Weighted<S>: Weigh<(usize, S)>,
+    S: Clone + Add<Output = S> + Ord + Default,
+{
+    /// returns id of the edge, new keys are added as vertices
+    pub fn add_edge(&mut self, from: K, to: K, weight: S) -> usize {
+        let u = self.add_vertex(from);
+        let v = self.add_vertex(to);
+        self.graph.add_edge(u, v, weight)
+    }
+}
+
+impl<K, S> LabeledGraph<K, DirectedWeightedListGraph<S>>
+where
+    K: Hash + Eq + Clone,
+    Weighted<S>: Weigh<(usize, S)>,
+    S: Clone + Add<Output = S> + Ord + Default,
+{
+    /// returns id of the edge, new keys are added as vertices
+    pub fn add_edge(&mut self, from: K, to: K, weight: S) -> usize {
+        let u = self.add_vertex(from);
+        let v = self.add_vertex(to);
+        self.graph.add_edge(u, v, weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        DirectedUnweightedListGraph, DirectedWeightedListGraph, UndirectedUnweightedListGraph,
+    };
+    use super::LabeledGraph;
+
+    #[test]
+    fn unweighted() {
+        let mut g = LabeledGraph::<String, UndirectedUnweightedListGraph>::new();
+        g.add_edge("a".to_string(), "b".to_string());
+        g.add_edge("b".to_string(), "c".to_string());
+        g.add_edge("x".to_string(), "y".to_string());
+        g.add_vertex("z".to_string());
+        assert_eq!(g.len(), 6);
+        assert_eq!(g.index(&"c".to_string()), Some(2));
+        assert_eq!(g.key(3), "x");
+
+        let (a, b, c) = ("a".to_string(), "b".to_string(), "c".to_string());
+        assert_eq!(g.bfs_links(&b), Some(vec![(&b, &a), (&b, &c)]));
+        assert_eq!(g.bfs_links(&"nowhere".to_string()), None);
+        assert_eq!(
+            g.bfs_path(&"a".to_string(), &"c".to_string()),
+            Some(vec![&"a".to_string(), &"b".to_string(), &"c".to_string()])
+        );
+        assert_eq!(g.bfs_path(&"a".to_string(), &"x".to_string()), None);
+        assert_eq!(g.bfs_path(&"a".to_string(), &"nowhere".to_string()), None);
+
+        let components = g.components();
+        assert_eq!(components.len(), 3);
+        assert_eq!(components[0].len(), 3);
+        assert_eq!(components[2], vec!["z"]);
+    }
+
+    #[test]
+    fn directed_and_weighted() {
+        let mut g = LabeledGraph::<u64, DirectedUnweightedListGraph>::new();
+        g.add_edge(1_000_000_000_000, 7);
+        g.add_edge(7, 1_000_000_000_000);
+        g.add_edge(7, 42);
+        let scc = g.scc();
+        assert_eq!(scc.len(), 2);
+        assert_eq!(scc[1], vec![&42]);
+
+        let mut g = LabeledGraph::<&str, DirectedWeightedListGraph<u32>>::new();
+        g.add_edge("s", "a", 4);
+        g.add_edge("s", "b", 1);
+        g.add_edge("b", "a", 2);
+        assert_eq!(
+            g.dijkstra_path(&"s", &"a"),
+            Some((3, vec![&"s", &"b", &"a"]))
+        );
+        assert_eq!(g.dijkstra_path(&"a", &"s"), None);
+        assert_eq!(g.graph().edge_count(), 3);
+    }
+}
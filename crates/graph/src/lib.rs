@@ -1,6 +1,39 @@
+pub mod algebra;
+pub mod bellman_ford;
 pub mod bfs;
+pub mod bipartite;
+pub mod centroid;
+pub mod clique;
+pub mod dag;
 pub mod dfs;
-// pub mod dijkstra;
+pub mod dial;
+pub mod diameter;
+pub mod dijkstra;
+pub mod dominator;
+pub mod dot;
+pub mod dsu;
+pub mod dynamic_connectivity;
+pub mod euler;
+pub mod grid;
+pub mod hld;
+pub mod json;
+pub mod labeled;
+pub mod link_cut;
+pub mod lowlink;
+pub mod maxflow;
+pub mod mincostflow;
+pub mod mst;
+pub mod parse;
+#[cfg(any(test, feature = "random"))]
+pub mod random;
+pub mod rerooting;
+pub mod scc;
+pub mod shortest_paths;
+pub mod tree;
+pub mod two_sat;
+
+pub use grid::GridGraph;
+pub use labeled::LabeledGraph;
 
 use std::{marker::PhantomData, ops::Add};
 
@@ -41,13 +74,63 @@ impl Weigh<usize> for Unweighted {
     const WEIGHTED: bool = false;
 }
 
-impl<S> Weigh<S> for Weighted<S> {
+impl<S> Weigh<(usize, S)> for Weighted<S> {
     const WEIGHTED: bool = true;
 }
 
 pub struct ListGraph<S, D: Direct, W: Weigh<S>> {
     _phantom: (PhantomData<D>, PhantomData<W>),
     inner: Vec<Vec<S>>,
+    /// `ids[u][i]` is the id of the edge stored in `inner[u][i]`
+    ids: Vec<Vec<usize>>,
+    /// `(from, to)` of each edge, indexed by id
+    ends: Vec<(usize, usize)>,
+}
+
+impl<S, D: Direct, W: Weigh<S>> ListGraph<S, D, W> {
+    /// number of edges added by `add_edge`
+    pub fn edge_count(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// `(from, to)` given to `add_edge` which returned `id`
+    pub fn edge(&self, id: usize) -> (usize, usize) {
+        self.ends[id]
+    }
+
+    /// ids of edges adjacent to `u`, in the same order as the neighbors
+    pub fn edge_ids(&self, u: usize) -> &[usize] {
+        &self.ids[u]
+    }
+
+    /// vertices adjacent to `u`, regardless of weights
+    pub fn neighbors(&self, u: usize) -> impl Iterator<Item = usize> + '_ {
+        self.ids[u].iter().map(move |&id| {
+            let (from, to) = self.ends[id];
+            if from == u {
+                to
+            } else {
+                from
+            }
+        })
+    }
+
+    /// add an isolated vertex, returns its index
+    pub fn add_vertex(&mut self) -> usize {
+        self.inner.push(Vec::new());
+        self.ids.push(Vec::new());
+        self.inner.len() - 1
+    }
+
+    fn push_edge(&mut self, from: usize, to: usize) -> usize {
+        let id = self.ends.len();
+        self.ends.push((from, to));
+        self.ids[from].push(id);
+        if !D::DIRECTED {
+            self.ids[to].push(id);
+        }
+        id
+    }
 }
 
 pub type UnweightedListGraph<D> = ListGraph<usize, D, Unweighted>;
@@ -65,6 +148,8 @@ impl<D: Direct> UnweightedListGraph<D> {
         Self {
             _phantom: (PhantomData, PhantomData),
             inner: vec![Vec::new(); n],
+            ids: vec![Vec::new(); n],
+            ends: Vec::new(),
         }
     }
 
@@ -74,9 +159,11 @@ impl<D: Direct> UnweightedListGraph<D> {
 }
 
 impl UndirectedUnweightedListGraph {
-    pub fn add_edge(&mut self, from: usize, to: usize) {
+    /// returns id of the edge
+    pub fn add_edge(&mut self, from: usize, to: usize) -> usize {
         self.inner[from].push(to);
         self.inner[to].push(from);
+        self.push_edge(from, to)
     }
 
     pub fn from_edges(n: usize, edges: &[(usize, usize)]) -> Self {
@@ -89,8 +176,10 @@ impl UndirectedUnweightedListGraph {
 }
 
 impl DirectedUnweightedListGraph {
-    pub fn add_edge(&mut self, from: usize, to: usize) {
+    /// returns id of the edge
+    pub fn add_edge(&mut self, from: usize, to: usize) -> usize {
         self.inner[from].push(to);
+        self.push_edge(from, to)
     }
 
     pub fn from_edges(n: usize, edges: &[(usize, usize)]) -> Self {
@@ -112,12 +201,29 @@ where
         Self {
             _phantom: (PhantomData, PhantomData),
             inner: vec![Vec::new(); n],
+            ids: vec![Vec::new(); n],
+            ends: Vec::new(),
         }
     }
 
     pub fn len(&self) -> usize {
         self.inner.len()
     }
+
+    /// `(from, to, weight)` of each edge, indexed by id
+    pub fn weighted_edges(&self) -> Vec<(usize, usize, S)> {
+        let mut weights = vec![None; self.edge_count()];
+        for (adj, ids) in self.inner.iter().zip(self.ids.iter()) {
+            for ((_, w), &id) in adj.iter().zip(ids.iter()) {
+                weights[id] = Some(w.clone());
+            }
+        }
+        self.ends
+            .iter()
+            .zip(weights)
+            .map(|(&(from, to), w)| (from, to, w.unwrap()))
+            .collect()
+    }
 }
 
 impl<S> UndirectedWeightedListGraph<S>
@@ -125,9 +231,11 @@ where
     Weighted<S>: Weigh<(usize, S)>,
     S: Clone + Add + Ord,
 {
-    pub fn add_edge(&mut self, from: usize, to: usize, weight: S) {
+    /// returns id of the edge
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: S) -> usize {
         self.inner[from].push((to, weight.clone()));
         self.inner[to].push((from, weight));
+        self.push_edge(from, to)
     }
 
     pub fn from_edges(n: usize, edges: &[(usize, usize, S)]) -> Self {
@@ -144,8 +252,10 @@ where
     Weighted<S>: Weigh<(usize, S)>,
     S: Clone + Add + Ord,
 {
-    pub fn add_edge(&mut self, from: usize, to: usize, weight: S) {
+    /// returns id of the edge
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: S) -> usize {
         self.inner[from].push((to, weight));
+        self.push_edge(from, to)
     }
 
     pub fn from_edges(n: usize, edges: &[(usize, usize, S)]) -> Self {
@@ -160,7 +270,3 @@ where
 // pub struct MatGraph {
 //     inner: Vec<Vec<Option<usize>>>,
 // }
-
-// pub struct GridGraoh<C> {
-//     inner: Vec<Vec<C>>,
-// }
@@ -0,0 +1,352 @@
+use std::mem::swap;
+
+use super::algebra::Monoid;
+
+const NIL: usize = usize::MAX;
+
+/// forest which supports link, cut and aggregation of values on a path
+/// in amortized `O(log n)`, by splay trees
+///
+/// the monoid need not be commutative, `path_fold(u, v)` folds values from `u` to `v`
+///
+/// ```ignore
+/// let mut lct = LinkCutTree::<Additive<i64>>::from_values(vec![1, 2, 3]);
+/// lct.link(0, 1);
+/// lct.link(1, 2);
+/// assert_eq!(lct.path_fold(0, 2), Some(6));
+/// ```
+pub struct LinkCutTree<M: Monoid> {
+    /// children on the splay tree
+    ch: Vec<[usize; 2]>,
+    /// parent on the splay tree, or path-parent for the root of a splay tree
+    par: Vec<usize>,
+    /// children must be swapped, `sum` and `rev_sum` of itself are already swapped
+    rev: Vec<bool>,
+    val: Vec<M::S>,
+    /// fold of the splay subtree from left to right
+    sum: Vec<M::S>,
+    /// fold of the splay subtree from right to left
+    rev_sum: Vec<M::S>,
+}
+
+impl<M: Monoid> LinkCutTree<M> {
+    /// `n` isolated vertices with the identity
+    pub fn new(n: usize) -> Self {
+        Self::from_values(vec![M::identity(); n])
+    }
+
+    pub fn from_values(values: Vec<M::S>) -> Self {
+        let n = values.len();
+        Self {
+            ch: vec![[NIL; 2]; n],
+            par: vec![NIL; n],
+            rev: vec![false; n],
+            sum: values.clone(),
+            rev_sum: values.clone(),
+            val: values,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.val.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.val.is_empty()
+    }
+
+    fn is_root(&self, x: usize) -> bool {
+        let p = self.par[x];
+        p == NIL || (self.ch[p][0] != x && self.ch[p][1] != x)
+    }
+
+    fn toggle(&mut self, x: usize) {
+        if x != NIL {
+            swap(&mut self.sum[x], &mut self.rev_sum[x]);
+            self.rev[x] ^= true;
+        }
+    }
+
+    fn push(&mut self, x: usize) {
+        if self.rev[x] {
+            self.ch[x].swap(0, 1);
+            let [l, r] = self.ch[x];
+            self.toggle(l);
+            self.toggle(r);
+            self.rev[x] = false;
+        }
+    }
+
+    fn update(&mut self, x: usize) {
+        let [l, r] = self.ch[x];
+        let mut sum = self.val[x].clone();
+        let mut rev_sum = self.val[x].clone();
+        if l != NIL {
+            sum = M::binary_operation(&self.sum[l], &sum);
+            rev_sum = M::binary_operation(&rev_sum, &self.rev_sum[l]);
+        }
+        if r != NIL {
+            sum = M::binary_operation(&sum, &self.sum[r]);
+            rev_sum = M::binary_operation(&self.rev_sum[r], &rev_sum);
+        }
+        self.sum[x] = sum;
+        self.rev_sum[x] = rev_sum;
+    }
+
+    fn rotate(&mut self, x: usize) {
+        let p = self.par[x];
+        let g = self.par[p];
+        let dir = (self.ch[p][1] == x) as usize;
+        let b = self.ch[x][dir ^ 1];
+        self.ch[p][dir] = b;
+        if b != NIL {
+            self.par[b] = p;
+        }
+        self.ch[x][dir ^ 1] = p;
+        self.par[p] = x;
+        self.par[x] = g;
+        if g != NIL {
+            if self.ch[g][0] == p {
+                self.ch[g][0] = x;
+            } else if self.ch[g][1] == p {
+                self.ch[g][1] = x;
+            }
+        }
+        self.update(p);
+        self.update(x);
+    }
+
+    fn splay(&mut self, x: usize) {
+        let mut path = vec![x];
+        let mut y = x;
+        while !self.is_root(y) {
+            y = self.par[y];
+            path.push(y);
+        }
+        while let Some(y) = path.pop() {
+            self.push(y);
+        }
+        while !self.is_root(x) {
+            let p = self.par[x];
+            if !self.is_root(p) {
+                let g = self.par[p];
+                if (self.ch[g][0] == p) == (self.ch[p][0] == x) {
+                    self.rotate(p);
+                } else {
+                    self.rotate(x);
+                }
+            }
+            self.rotate(x);
+        }
+    }
+
+    /// make the path from the root to `x` preferred, `x` becomes the root of its splay tree
+    fn access(&mut self, x: usize) {
+        let mut last = NIL;
+        let mut y = x;
+        while y != NIL {
+            self.splay(y);
+            self.ch[y][1] = last;
+            self.update(y);
+            last = y;
+            y = self.par[y];
+        }
+        self.splay(x);
+    }
+
+    /// make `v` the root of its tree
+    pub fn evert(&mut self, v: usize) {
+        self.access(v);
+        self.toggle(v);
+    }
+
+    /// root of the tree containing `v`
+    pub fn root(&mut self, v: usize) -> usize {
+        self.access(v);
+        let mut x = v;
+        loop {
+            self.push(x);
+            if self.ch[x][0] == NIL {
+                break;
+            }
+            x = self.ch[x][0];
+        }
+        self.splay(x);
+        x
+    }
+
+    pub fn connected(&mut self, u: usize, v: usize) -> bool {
+        u == v || self.root(u) == self.root(v)
+    }
+
+    /// add edge `(u, v)`, returns false and does nothing if they are already connected
+    pub fn link(&mut self, u: usize, v: usize) -> bool {
+        if self.connected(u, v) {
+            return false;
+        }
+        self.evert(u);
+        self.par[u] = v;
+        true
+    }
+
+    /// remove edge `(u, v)`, returns false and does nothing if there is no such edge
+    pub fn cut(&mut self, u: usize, v: usize) -> bool {
+        if u == v || !self.connected(u, v) {
+            return false;
+        }
+        self.evert(u);
+        self.access(v);
+        // the path is u -> v, so u is the only vertex on the left of v if adjacent
+        if self.ch[v][0] != u {
+            return false;
+        }
+        self.push(u);
+        if self.ch[u][1] != NIL {
+            return false;
+        }
+        self.ch[v][0] = NIL;
+        self.par[u] = NIL;
+        self.update(v);
+        true
+    }
+
+    pub fn get(&self, v: usize) -> &M::S {
+        &self.val[v]
+    }
+
+    pub fn set(&mut self, v: usize, x: M::S) {
+        self.access(v);
+        self.val[v] = x;
+        self.update(v);
+    }
+
+    /// fold of values on the path from `u` to `v`, both ends inclusive,
+    /// `None` if they are not connected
+    pub fn path_fold(&mut self, u: usize, v: usize) -> Option<M::S> {
+        if !self.connected(u, v) {
+            return None;
+        }
+        self.evert(u);
+        self.access(v);
+        Some(self.sum[v].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use rand::Rng;
+
+    use super::super::{
+        algebra::{Additive, Monoid},
+        random::test_rng,
+    };
+    use super::LinkCutTree;
+
+    const MOD: u64 = 998_244_353;
+
+    /// composition of `x -> a x + b`, applied from left to right
+    struct Affine;
+
+    impl Monoid for Affine {
+        type S = (u64, u64);
+
+        fn identity() -> Self::S {
+            (1, 0)
+        }
+
+        fn binary_operation(x: &Self::S, y: &Self::S) -> Self::S {
+            (x.0 * y.0 % MOD, (x.1 * y.0 + y.1) % MOD)
+        }
+    }
+
+    fn naive_path(adj: &[Vec<usize>], u: usize, v: usize) -> Option<Vec<usize>> {
+        let mut prev = vec![usize::MAX; adj.len()];
+        prev[u] = u;
+        let mut queue = VecDeque::new();
+        queue.push_back(u);
+        while let Some(x) = queue.pop_front() {
+            for &y in adj[x].iter() {
+                if prev[y] == usize::MAX {
+                    prev[y] = x;
+                    queue.push_back(y);
+                }
+            }
+        }
+        if prev[v] == usize::MAX {
+            return None;
+        }
+        let mut path = vec![v];
+        while *path.last().unwrap() != u {
+            path.push(prev[*path.last().unwrap()]);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    #[test]
+    fn sum() {
+        let mut lct = LinkCutTree::<Additive<i64>>::from_values(vec![1, 2, 3, 4]);
+        assert!(lct.link(0, 1));
+        assert!(lct.link(1, 2));
+        assert!(!lct.link(0, 2));
+        assert_eq!(lct.path_fold(0, 2), Some(6));
+        assert_eq!(lct.path_fold(0, 3), None);
+        assert!(lct.link(3, 1));
+        assert_eq!(lct.path_fold(3, 2), Some(9));
+        assert!(!lct.cut(0, 2));
+        assert!(lct.cut(1, 2));
+        assert_eq!(lct.path_fold(3, 2), None);
+        lct.set(1, 10);
+        assert_eq!(lct.path_fold(0, 3), Some(15));
+        assert_eq!(*lct.get(1), 10);
+        lct.evert(3);
+        assert_eq!(lct.root(0), 3);
+    }
+
+    #[test]
+    fn random_affine() {
+        let mut rng = test_rng(13);
+        let n = 12;
+        let mut values: Vec<(u64, u64)> = (0..n)
+            .map(|_| (rng.gen_range(0, MOD), rng.gen_range(0, MOD)))
+            .collect();
+        let mut lct = LinkCutTree::<Affine>::from_values(values.clone());
+        let mut adj = vec![Vec::new(); n];
+        for _ in 0..3000 {
+            let u = rng.gen_range(0, n);
+            let v = rng.gen_range(0, n);
+            match rng.gen_range(0, 4) {
+                0 => {
+                    let expected = u != v && naive_path(&adj, u, v).is_none();
+                    assert_eq!(lct.link(u, v), expected);
+                    if expected {
+                        adj[u].push(v);
+                        adj[v].push(u);
+                    }
+                }
+                1 => {
+                    let expected = adj[u].contains(&v);
+                    assert_eq!(lct.cut(u, v), expected);
+                    if expected {
+                        adj[u].retain(|&x| x != v);
+                        adj[v].retain(|&x| x != u);
+                    }
+                }
+                2 => {
+                    values[u] = (rng.gen_range(0, MOD), rng.gen_range(0, MOD));
+                    lct.set(u, values[u]);
+                }
+                _ => {
+                    let expected = naive_path(&adj, u, v).map(|p| {
+                        p.iter().fold(Affine::identity(), |acc, &x| {
+                            Affine::binary_operation(&acc, &values[x])
+                        })
+                    });
+                    assert_eq!(lct.path_fold(u, v), expected);
+                }
+            }
+        }
+    }
+}
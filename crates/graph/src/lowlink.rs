@@ -0,0 +1,308 @@
+use std::cmp::min;
+
+use super::UndirectedUnweightedListGraph;
+
+/// result of lowlink on undirected graph
+///
+/// multiple edges are distinguished by their ids,
+/// so two vertices connected by two edges are never separated by a bridge
+#[derive(Debug, Clone)]
+pub struct LowLink {
+    ord: Vec<usize>,
+    low: Vec<usize>,
+    bridges: Vec<usize>,
+    articulation_points: Vec<usize>,
+    blocks: Vec<Vec<usize>>,
+}
+
+impl LowLink {
+    /// preorder of DFS
+    pub fn ord(&self) -> &[usize] {
+        &self.ord
+    }
+
+    /// smallest `ord` reachable by going down the DFS tree and using at most one back edge
+    pub fn low(&self) -> &[usize] {
+        &self.low
+    }
+
+    /// ids of bridges, sorted
+    pub fn bridges(&self) -> &[usize] {
+        &self.bridges
+    }
+
+    /// sorted
+    pub fn articulation_points(&self) -> &[usize] {
+        &self.articulation_points
+    }
+
+    /// ids of edges in each biconnected component, self loops are not included
+    pub fn blocks(&self) -> &[Vec<usize>] {
+        &self.blocks
+    }
+}
+
+impl UndirectedUnweightedListGraph {
+    pub fn lowlink(&self) -> LowLink {
+        let n = self.len();
+        const UNSEEN: usize = usize::MAX;
+        let mut ord = vec![UNSEEN; n];
+        let mut low = vec![0; n];
+        let mut is_articulation = vec![false; n];
+        let mut bridges = Vec::new();
+        let mut blocks = Vec::new();
+        let mut edge_stack = Vec::new();
+        // (vertex, index of next neighbor, id of the edge from parent)
+        let mut call: Vec<(usize, usize, Option<usize>)> = Vec::new();
+        let mut now = 0;
+        for root in 0..n {
+            if ord[root] != UNSEEN {
+                continue;
+            }
+            ord[root] = now;
+            low[root] = now;
+            now += 1;
+            call.push((root, 0, None));
+            let mut root_children = 0;
+            while let Some((v, i, parent_edge)) = call.last_mut() {
+                let (v, parent_edge) = (*v, *parent_edge);
+                if let Some(&to) = self.inner[v].get(*i) {
+                    let e = self.ids[v][*i];
+                    *i += 1;
+                    if Some(e) == parent_edge {
+                        continue;
+                    }
+                    if ord[to] == UNSEEN {
+                        edge_stack.push(e);
+                        ord[to] = now;
+                        low[to] = now;
+                        now += 1;
+                        call.push((to, 0, Some(e)));
+                        if v == root {
+                            root_children += 1;
+                        }
+                    } else if ord[to] < ord[v] {
+                        edge_stack.push(e);
+                        low[v] = min(low[v], ord[to]);
+                    }
+                } else {
+                    call.pop();
+                    if let (Some(&(p, _, _)), Some(e)) = (call.last(), parent_edge) {
+                        low[p] = min(low[p], low[v]);
+                        if low[v] > ord[p] {
+                            bridges.push(e);
+                        }
+                        if low[v] >= ord[p] {
+                            if p != root {
+                                is_articulation[p] = true;
+                            }
+                            let mut block = Vec::new();
+                            while let Some(x) = edge_stack.pop() {
+                                block.push(x);
+                                if x == e {
+                                    break;
+                                }
+                            }
+                            blocks.push(block);
+                        }
+                    }
+                }
+            }
+            if root_children >= 2 {
+                is_articulation[root] = true;
+            }
+        }
+        bridges.sort_unstable();
+        LowLink {
+            ord,
+            low,
+            bridges,
+            articulation_points: (0..n).filter(|&v| is_articulation[v]).collect(),
+            blocks,
+        }
+    }
+
+    /// 2-edge-connected components, returns `(number of components, id of component for each vertex)`
+    pub fn two_edge_connected_component_ids(&self) -> (usize, Vec<usize>) {
+        let lowlink = self.lowlink();
+        let mut is_bridge = vec![false; self.edge_count()];
+        for &e in lowlink.bridges() {
+            is_bridge[e] = true;
+        }
+        const UNSEEN: usize = usize::MAX;
+        let mut ids = vec![UNSEEN; self.len()];
+        let mut k = 0;
+        let mut stack = Vec::new();
+        for root in 0..self.len() {
+            if ids[root] != UNSEEN {
+                continue;
+            }
+            ids[root] = k;
+            stack.push(root);
+            while let Some(u) = stack.pop() {
+                for (&v, &e) in self.inner[u].iter().zip(self.ids[u].iter()) {
+                    if !is_bridge[e] && ids[v] == UNSEEN {
+                        ids[v] = k;
+                        stack.push(v);
+                    }
+                }
+            }
+            k += 1;
+        }
+        (k, ids)
+    }
+
+    /// vertices of each 2-edge-connected component
+    pub fn two_edge_connected_components(&self) -> Vec<Vec<usize>> {
+        let (k, ids) = self.two_edge_connected_component_ids();
+        let mut groups = vec![Vec::new(); k];
+        for (v, &id) in ids.iter().enumerate() {
+            groups[id].push(v);
+        }
+        groups
+    }
+
+    /// ids of 2-edge-connected components and the forest whose vertices are the components,
+    /// and edges are the bridges
+    pub fn bridge_tree(&self) -> (Vec<usize>, UndirectedUnweightedListGraph) {
+        let (k, ids) = self.two_edge_connected_component_ids();
+        let mut tree = UndirectedUnweightedListGraph::new(k);
+        for e in 0..self.edge_count() {
+            let (u, v) = self.edge(e);
+            if ids[u] != ids[v] {
+                tree.add_edge(ids[u], ids[v]);
+            }
+        }
+        (ids, tree)
+    }
+
+    /// vertices of each biconnected component
+    ///
+    /// a vertex without edges (except self loops) forms a component by itself
+    pub fn biconnected_components(&self) -> Vec<Vec<usize>> {
+        let lowlink = self.lowlink();
+        let mut covered = vec![false; self.len()];
+        let mut res: Vec<Vec<usize>> = lowlink
+            .blocks()
+            .iter()
+            .map(|block| {
+                let mut vs: Vec<_> = block
+                    .iter()
+                    .flat_map(|&e| {
+                        let (u, v) = self.edge(e);
+                        vec![u, v]
+                    })
+                    .collect();
+                vs.sort_unstable();
+                vs.dedup();
+                for &v in vs.iter() {
+                    covered[v] = true;
+                }
+                vs
+            })
+            .collect();
+        res.extend((0..self.len()).filter(|&v| !covered[v]).map(|v| vec![v]));
+        res
+    }
+
+    /// biconnected components and the block-cut tree
+    ///
+    /// vertex `v < n` of the tree is the original vertex `v`,
+    /// and vertex `n + i` is the `i`-th component, adjacent to the vertices in it
+    pub fn block_cut_tree(&self) -> (Vec<Vec<usize>>, UndirectedUnweightedListGraph) {
+        let n = self.len();
+        let blocks = self.biconnected_components();
+        let mut tree = UndirectedUnweightedListGraph::new(n + blocks.len());
+        for (i, block) in blocks.iter().enumerate() {
+            for &v in block.iter() {
+                tree.add_edge(v, n + i);
+            }
+        }
+        (blocks, tree)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::UndirectedUnweightedListGraph;
+
+    fn sample() -> UndirectedUnweightedListGraph {
+        // two triangles joined by a bridge, another bridge to a double edge,
+        // a self loop and an isolated vertex
+        let edges = &[
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (5, 6),
+            (6, 7),
+            (7, 6),
+            (4, 4),
+        ];
+        UndirectedUnweightedListGraph::from_edges(9, edges)
+    }
+
+    #[test]
+    fn bridges_and_articulation_points() {
+        let g = sample();
+        let lowlink = g.lowlink();
+        assert_eq!(lowlink.bridges(), &[3, 7]);
+        assert_eq!(lowlink.articulation_points(), &[2, 3, 5, 6]);
+
+        let path = UndirectedUnweightedListGraph::from_edges(3, &[(0, 1), (1, 2)]);
+        let lowlink = path.lowlink();
+        assert_eq!(lowlink.bridges(), &[0, 1]);
+        assert_eq!(lowlink.articulation_points(), &[1]);
+    }
+
+    #[test]
+    fn two_edge_connected() {
+        let g = sample();
+        assert_eq!(
+            g.two_edge_connected_components(),
+            vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7], vec![8]]
+        );
+        let (ids, tree) = g.bridge_tree();
+        assert_eq!(ids, vec![0, 0, 0, 1, 1, 1, 2, 2, 3]);
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.edge_count(), 2);
+        assert_eq!(tree.edge(0), (0, 1));
+        assert_eq!(tree.edge(1), (1, 2));
+    }
+
+    #[test]
+    fn biconnected() {
+        let g = sample();
+        let mut blocks = g.biconnected_components();
+        blocks.sort();
+        assert_eq!(
+            blocks,
+            vec![
+                vec![0, 1, 2],
+                vec![2, 3],
+                vec![3, 4, 5],
+                vec![5, 6],
+                vec![6, 7],
+                vec![8]
+            ]
+        );
+        let (blocks, tree) = g.block_cut_tree();
+        assert_eq!(tree.len(), 9 + blocks.len());
+        assert_eq!(tree.edge_count(), 13);
+        // every edge is a bridge, so it is a forest
+        assert_eq!(tree.lowlink().bridges().len(), tree.edge_count());
+    }
+
+    #[test]
+    fn long_path() {
+        let n = 1_000_000;
+        let edges: Vec<_> = (0..n - 1).map(|i| (i, i + 1)).collect();
+        let g = UndirectedUnweightedListGraph::from_edges(n, &edges);
+        let lowlink = g.lowlink();
+        assert_eq!(lowlink.bridges().len(), n - 1);
+        assert_eq!(lowlink.articulation_points().len(), n - 2);
+    }
+}
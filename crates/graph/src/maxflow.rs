@@ -0,0 +1,319 @@
+use std::{
+    cmp::min,
+    collections::VecDeque,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+use ::bitset::BitSet;
+
+/// integer type which can be used as capacity of flow
+pub trait Capacity:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + AddAssign + SubAssign
+{
+    fn zero() -> Self;
+    fn max_value() -> Self;
+}
+
+macro_rules! impl_capacity {
+    ($($ty:ty),*) => {
+        $(
+            impl Capacity for $ty {
+                #[inline]
+                fn zero() -> Self {
+                    0
+                }
+
+                #[inline]
+                fn max_value() -> Self {
+                    <$ty>::MAX
+                }
+            }
+        )*
+    };
+}
+
+impl_capacity!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// edge of flow network seen from outside
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge<Cap> {
+    pub from: usize,
+    pub to: usize,
+    pub cap: Cap,
+    pub flow: Cap,
+}
+
+#[derive(Debug, Clone)]
+struct InnerEdge<Cap> {
+    to: usize,
+    /// index of reverse edge in `g[to]`
+    rev: usize,
+    /// residual capacity
+    cap: Cap,
+}
+
+/// flow network for max flow by Dinic's algorithm
+///
+/// ```ignore
+/// let mut g = MfGraph::new(3);
+/// g.add_edge(0, 1, 2);
+/// g.add_edge(1, 2, 1);
+/// assert_eq!(g.flow(0, 2), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MfGraph<Cap> {
+    n: usize,
+    /// position of each edge in `g`
+    pos: Vec<(usize, usize)>,
+    g: Vec<Vec<InnerEdge<Cap>>>,
+}
+
+impl<Cap: Capacity> MfGraph<Cap> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            pos: Vec::new(),
+            g: vec![Vec::new(); n],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// returns id of the edge
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: Cap) -> usize {
+        assert!(from < self.n && to < self.n);
+        assert!(Cap::zero() <= cap);
+        let id = self.pos.len();
+        self.pos.push((from, self.g[from].len()));
+        let rev = self.g[to].len() + if from == to { 1 } else { 0 };
+        self.g[from].push(InnerEdge { to, rev, cap });
+        let rev = self.g[from].len() - 1;
+        self.g[to].push(InnerEdge {
+            to: from,
+            rev,
+            cap: Cap::zero(),
+        });
+        id
+    }
+
+    /// capacity and current flow of the edge
+    pub fn get_edge(&self, id: usize) -> Edge<Cap> {
+        let (from, i) = self.pos[id];
+        let e = &self.g[from][i];
+        let re = &self.g[e.to][e.rev];
+        Edge {
+            from,
+            to: e.to,
+            cap: e.cap + re.cap,
+            flow: re.cap,
+        }
+    }
+
+    /// all edges in order of id
+    pub fn edges(&self) -> Vec<Edge<Cap>> {
+        (0..self.pos.len()).map(|id| self.get_edge(id)).collect()
+    }
+
+    /// overwrite capacity and flow of the edge, without changing other edges
+    pub fn change_edge(&mut self, id: usize, new_cap: Cap, new_flow: Cap) {
+        assert!(Cap::zero() <= new_flow && new_flow <= new_cap);
+        let (from, i) = self.pos[id];
+        let (to, rev) = (self.g[from][i].to, self.g[from][i].rev);
+        self.g[from][i].cap = new_cap - new_flow;
+        self.g[to][rev].cap = new_flow;
+    }
+
+    /// max flow from `s` to `t`, added to the current flow
+    pub fn flow(&mut self, s: usize, t: usize) -> Cap {
+        self.flow_with_limit(s, t, Cap::max_value())
+    }
+
+    /// max flow from `s` to `t` which does not exceed `flow_limit`
+    pub fn flow_with_limit(&mut self, s: usize, t: usize, flow_limit: Cap) -> Cap {
+        assert!(s < self.n && t < self.n);
+        assert_ne!(s, t);
+        let mut calc = FlowCalculator {
+            graph: self,
+            s,
+            t,
+            level: Vec::new(),
+            iter: Vec::new(),
+        };
+        let mut flow = Cap::zero();
+        while flow < flow_limit {
+            calc.bfs();
+            if calc.level[t] == UNSEEN {
+                break;
+            }
+            calc.iter = vec![0; calc.graph.n];
+            while flow < flow_limit {
+                let f = calc.dfs(t, flow_limit - flow);
+                if f == Cap::zero() {
+                    break;
+                }
+                flow += f;
+            }
+        }
+        flow
+    }
+
+    /// vertices reachable from `s` on the residual network
+    ///
+    /// after `flow(s, t)`, this is the source side of a minimum cut
+    pub fn min_cut(&self, s: usize) -> BitSet {
+        let mut visited = BitSet::new(self.n);
+        let mut queue = VecDeque::new();
+        visited.entry(s);
+        queue.push_back(s);
+        while let Some(v) = queue.pop_front() {
+            for e in self.g[v].iter() {
+                if e.cap != Cap::zero() && !visited[e.to] {
+                    visited.entry(e.to);
+                    queue.push_back(e.to);
+                }
+            }
+        }
+        visited
+    }
+}
+
+const UNSEEN: usize = usize::MAX;
+
+struct FlowCalculator<'a, Cap> {
+    graph: &'a mut MfGraph<Cap>,
+    s: usize,
+    t: usize,
+    level: Vec<usize>,
+    iter: Vec<usize>,
+}
+
+impl<Cap: Capacity> FlowCalculator<'_, Cap> {
+    fn bfs(&mut self) {
+        self.level = vec![UNSEEN; self.graph.n];
+        self.level[self.s] = 0;
+        let mut queue = VecDeque::new();
+        queue.push_back(self.s);
+        while let Some(v) = queue.pop_front() {
+            for e in self.graph.g[v].iter() {
+                if e.cap == Cap::zero() || self.level[e.to] != UNSEEN {
+                    continue;
+                }
+                self.level[e.to] = self.level[v] + 1;
+                if e.to == self.t {
+                    return;
+                }
+                queue.push_back(e.to);
+            }
+        }
+    }
+
+    /// push flow back from `v` to `s`, recursion depth is at most the level of `t`
+    fn dfs(&mut self, v: usize, up: Cap) -> Cap {
+        if v == self.s {
+            return up;
+        }
+        let mut res = Cap::zero();
+        let level_v = self.level[v];
+        while self.iter[v] < self.graph.g[v].len() {
+            let i = self.iter[v];
+            let (to, rev) = (self.graph.g[v][i].to, self.graph.g[v][i].rev);
+            let rcap = self.graph.g[to][rev].cap;
+            if level_v <= self.level[to] || rcap == Cap::zero() {
+                self.iter[v] += 1;
+                continue;
+            }
+            let d = self.dfs(to, min(up - res, rcap));
+            if d == Cap::zero() {
+                self.iter[v] += 1;
+                continue;
+            }
+            self.graph.g[v][i].cap += d;
+            self.graph.g[to][rev].cap -= d;
+            res += d;
+            if res == up {
+                return res;
+            }
+            self.iter[v] += 1;
+        }
+        self.level[v] = self.graph.n;
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Edge, MfGraph};
+
+    #[test]
+    fn simple() {
+        let mut g = MfGraph::new(4);
+        assert_eq!(g.add_edge(0, 1, 1), 0);
+        assert_eq!(g.add_edge(0, 2, 1), 1);
+        assert_eq!(g.add_edge(1, 3, 1), 2);
+        assert_eq!(g.add_edge(2, 3, 1), 3);
+        assert_eq!(g.add_edge(1, 2, 1), 4);
+        assert_eq!(g.flow(0, 3), 2);
+
+        let e = Edge {
+            from: 0,
+            to: 1,
+            cap: 1,
+            flow: 1,
+        };
+        assert_eq!(g.get_edge(0), e);
+        let e = Edge {
+            from: 1,
+            to: 2,
+            cap: 1,
+            flow: 0,
+        };
+        assert_eq!(g.get_edge(4), e);
+
+        let cut = g.min_cut(0);
+        assert_eq!(cut.iter().collect::<Vec<_>>(), vec![0]);
+    }
+
+    #[test]
+    fn limit_and_change() {
+        let mut g = MfGraph::new(3);
+        g.add_edge(0, 1, 2);
+        let e = g.add_edge(1, 2, 5i64);
+        assert_eq!(g.flow_with_limit(0, 2, 1), 1);
+        assert_eq!(g.get_edge(e).flow, 1);
+        assert_eq!(g.flow(0, 2), 1);
+
+        // source side of the cut is {0}, since 0 -> 1 is saturated
+        assert_eq!(g.min_cut(0).iter().collect::<Vec<_>>(), vec![0]);
+
+        g.change_edge(0, 10, 2);
+        g.change_edge(e, 4, 2);
+        assert_eq!(g.flow(0, 2), 2);
+        assert_eq!(g.get_edge(e).flow, 4);
+        assert_eq!(g.min_cut(0).iter().collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    #[test]
+    fn self_loop_and_bipartite() {
+        let mut g = MfGraph::new(8);
+        g.add_edge(0, 0, 100);
+        // 1..=3 on left, 4..=6 on right
+        for &(l, r) in &[(1, 4), (1, 5), (2, 4), (3, 4), (3, 6)] {
+            g.add_edge(l, r, 1);
+        }
+        for l in 1..=3 {
+            g.add_edge(0, l, 1);
+        }
+        for r in 4..=6 {
+            g.add_edge(r, 7, 1);
+        }
+        assert_eq!(g.flow(0, 7), 3);
+        assert_eq!(g.get_edge(0).flow, 0);
+        assert_eq!(g.flow(0, 7), 0);
+    }
+}
@@ -0,0 +1,317 @@
+use std::{
+    cmp::{min, Reverse},
+    collections::BinaryHeap,
+    ops::{Mul, Neg},
+};
+
+use super::{dijkstra::is_shorter, maxflow::Capacity};
+
+/// edge of min cost flow network seen from outside
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge<Cap, Cost> {
+    pub from: usize,
+    pub to: usize,
+    pub cap: Cap,
+    pub flow: Cap,
+    pub cost: Cost,
+}
+
+#[derive(Debug, Clone)]
+struct InnerEdge<Cap, Cost> {
+    to: usize,
+    rev: usize,
+    cap: Cap,
+    cost: Cost,
+}
+
+/// flow network for min cost flow by primal-dual method
+///
+/// negative costs are allowed as long as there is no negative cycle
+#[derive(Debug, Clone)]
+pub struct McfGraph<Cap, Cost> {
+    n: usize,
+    pos: Vec<(usize, usize)>,
+    g: Vec<Vec<InnerEdge<Cap, Cost>>>,
+}
+
+impl<Cap, Cost> McfGraph<Cap, Cost>
+where
+    Cap: Capacity + Into<Cost>,
+    Cost: Capacity + Mul<Output = Cost> + Neg<Output = Cost>,
+{
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            pos: Vec::new(),
+            g: vec![Vec::new(); n],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// returns id of the edge
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: Cap, cost: Cost) -> usize {
+        assert!(from < self.n && to < self.n);
+        assert!(Cap::zero() <= cap);
+        let id = self.pos.len();
+        self.pos.push((from, self.g[from].len()));
+        let rev = self.g[to].len() + if from == to { 1 } else { 0 };
+        self.g[from].push(InnerEdge { to, rev, cap, cost });
+        let rev = self.g[from].len() - 1;
+        self.g[to].push(InnerEdge {
+            to: from,
+            rev,
+            cap: Cap::zero(),
+            cost: -cost,
+        });
+        id
+    }
+
+    /// capacity, current flow and cost of the edge
+    pub fn get_edge(&self, id: usize) -> Edge<Cap, Cost> {
+        let (from, i) = self.pos[id];
+        let e = &self.g[from][i];
+        let re = &self.g[e.to][e.rev];
+        Edge {
+            from,
+            to: e.to,
+            cap: e.cap + re.cap,
+            flow: re.cap,
+            cost: e.cost,
+        }
+    }
+
+    /// all edges in order of id
+    pub fn edges(&self) -> Vec<Edge<Cap, Cost>> {
+        (0..self.pos.len()).map(|id| self.get_edge(id)).collect()
+    }
+
+    /// flow as much as possible but not exceeding `flow_limit` from `s` to `t` with the minimum cost,
+    /// returns `(flow, cost)`
+    pub fn flow(&mut self, s: usize, t: usize, flow_limit: Cap) -> (Cap, Cost) {
+        *self.slope_with_limit(s, t, flow_limit).last().unwrap()
+    }
+
+    /// the cost as a function of the amount of max flow, see `slope_with_limit`
+    pub fn slope(&mut self, s: usize, t: usize) -> Vec<(Cap, Cost)> {
+        self.slope_with_limit(s, t, Cap::max_value())
+    }
+
+    /// the cost as a function of the amount of flow is piecewise linear and convex,
+    /// returns its bending points `(flow, cost)` starting from `(0, 0)`
+    pub fn slope_with_limit(&mut self, s: usize, t: usize, flow_limit: Cap) -> Vec<(Cap, Cost)> {
+        assert!(s < self.n && t < self.n);
+        assert_ne!(s, t);
+        let mut potential = self.initial_potential(s);
+        let mut prev = vec![(0, 0); self.n];
+        let mut flow = Cap::zero();
+        let mut cost = Cost::zero();
+        let mut prev_cost_per_flow = None;
+        let mut result = vec![(flow, cost)];
+        while flow < flow_limit {
+            if !self.dual_ref(s, t, &mut potential, &mut prev) {
+                break;
+            }
+            let mut c = flow_limit - flow;
+            let mut v = t;
+            while v != s {
+                let (u, i) = prev[v];
+                c = min(c, self.g[u][i].cap);
+                v = u;
+            }
+            let mut v = t;
+            while v != s {
+                let (u, i) = prev[v];
+                let rev = self.g[u][i].rev;
+                self.g[u][i].cap -= c;
+                self.g[v][rev].cap += c;
+                v = u;
+            }
+            // potential[s] is always zero
+            let d = potential[t];
+            flow += c;
+            cost += c.into() * d;
+            if prev_cost_per_flow == Some(d) {
+                result.pop();
+            }
+            result.push((flow, cost));
+            prev_cost_per_flow = Some(d);
+        }
+        result
+    }
+
+    /// shortest distances from `s` by Bellman-Ford if the residual network has a negative edge
+    fn initial_potential(&self, s: usize) -> Vec<Cost> {
+        let has_negative = self
+            .g
+            .iter()
+            .flatten()
+            .any(|e| e.cap > Cap::zero() && e.cost < Cost::zero());
+        let mut potential = vec![Cost::zero(); self.n];
+        if !has_negative {
+            return potential;
+        }
+        let mut dist: Vec<Option<Cost>> = vec![None; self.n];
+        dist[s] = Some(Cost::zero());
+        for round in 0.. {
+            let mut updated = false;
+            for u in 0..self.n {
+                let du = match dist[u] {
+                    Some(du) => du,
+                    None => continue,
+                };
+                for e in self.g[u].iter().filter(|e| e.cap > Cap::zero()) {
+                    let cand = du + e.cost;
+                    if is_shorter(&cand, &dist[e.to]) {
+                        dist[e.to] = Some(cand);
+                        updated = true;
+                    }
+                }
+            }
+            if !updated {
+                break;
+            }
+            assert!(round < self.n, "negative cycle in the residual network");
+        }
+        for (p, d) in potential.iter_mut().zip(dist) {
+            if let Some(d) = d {
+                *p = d;
+            }
+        }
+        potential
+    }
+
+    /// Dijkstra with reduced costs `cost + potential[from] - potential[to] >= 0`,
+    /// then update potential to the shortest distances from `s`
+    fn dual_ref(
+        &self,
+        s: usize,
+        t: usize,
+        potential: &mut [Cost],
+        prev: &mut [(usize, usize)],
+    ) -> bool {
+        let mut dist: Vec<Option<Cost>> = vec![None; self.n];
+        let mut visited = vec![false; self.n];
+        let mut heap = BinaryHeap::new();
+        dist[s] = Some(Cost::zero());
+        heap.push(Reverse((Cost::zero(), s)));
+        while let Some(Reverse((d, v))) = heap.pop() {
+            if visited[v] {
+                continue;
+            }
+            visited[v] = true;
+            for (i, e) in self.g[v].iter().enumerate() {
+                if e.cap == Cap::zero() {
+                    continue;
+                }
+                let cand = d + e.cost + potential[v] - potential[e.to];
+                if is_shorter(&cand, &dist[e.to]) {
+                    dist[e.to] = Some(cand);
+                    prev[e.to] = (v, i);
+                    heap.push(Reverse((cand, e.to)));
+                }
+            }
+        }
+        if !visited[t] {
+            return false;
+        }
+        for v in 0..self.n {
+            if let (true, Some(d)) = (visited[v], dist[v]) {
+                potential[v] += d;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Edge, McfGraph};
+
+    #[test]
+    fn simple() {
+        let mut g = McfGraph::new(4);
+        g.add_edge(0, 1, 1, 1);
+        g.add_edge(0, 2, 1, 1);
+        g.add_edge(1, 3, 1, 1);
+        g.add_edge(2, 3, 1, 1);
+        g.add_edge(1, 2, 1, 1);
+        assert_eq!(g.slope_with_limit(0, 3, 10), vec![(0, 0), (2, 4)]);
+        let e = Edge {
+            from: 0,
+            to: 1,
+            cap: 1,
+            flow: 1,
+            cost: 1,
+        };
+        assert_eq!(g.get_edge(0), e);
+        assert_eq!(g.get_edge(4).flow, 0);
+    }
+
+    #[test]
+    fn slope() {
+        let mut g = McfGraph::<i32, i64>::new(3);
+        g.add_edge(0, 1, 2, 1);
+        g.add_edge(1, 2, 1, 2);
+        g.add_edge(1, 2, 3, 5);
+        g.add_edge(0, 2, 1, 10);
+        assert_eq!(g.slope(0, 2), vec![(0, 0), (1, 3), (2, 9), (3, 19)]);
+        assert_eq!(g.flow(0, 2, 10), (0, 0));
+    }
+
+    #[test]
+    fn negative_cost() {
+        let mut g = McfGraph::<i64, i64>::new(3);
+        g.add_edge(0, 1, 2, -3);
+        g.add_edge(1, 2, 1, 1);
+        g.add_edge(0, 2, 1, 5);
+        g.add_edge(1, 2, 1, 4);
+        assert_eq!(g.flow(0, 2, 1), (1, -2));
+        // residual network has negative edges now
+        assert_eq!(g.flow(0, 2, 1), (1, 1));
+        assert_eq!(g.slope(0, 2), vec![(0, 0), (1, 5)]);
+        assert_eq!(
+            g.edges().iter().map(|e| e.flow).collect::<Vec<_>>(),
+            vec![2, 1, 1, 1]
+        );
+    }
+
+    #[test]
+    fn same_as_brute_force() {
+        // assignment problem, compared with all permutations
+        let cost = [[4, -1, 3], [2, 0, 5], [3, 2, -2]];
+        let mut g = McfGraph::<i32, i32>::new(8);
+        for (i, row) in cost.iter().enumerate() {
+            g.add_edge(6, i, 1, 0);
+            g.add_edge(3 + i, 7, 1, 0);
+            for (j, &c) in row.iter().enumerate() {
+                g.add_edge(i, 3 + j, 1, c);
+            }
+        }
+        let perms = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+        let best = perms
+            .iter()
+            .map(|p| {
+                p.iter()
+                    .zip(cost.iter())
+                    .map(|(&j, row)| row[j])
+                    .sum::<i32>()
+            })
+            .min()
+            .unwrap();
+        assert_eq!(g.flow(6, 7, 3), (3, best));
+    }
+}
@@ -0,0 +1,157 @@
+use std::{cmp::Reverse, collections::BinaryHeap, ops::Add};
+
+use super::{dsu::Dsu, UndirectedWeightedListGraph, Weigh, Weighted};
+
+/// minimum spanning forest by Kruskal's algorithm
+///
+/// returns total weight and indices of chosen edges in `edges`,
+/// `S::default()` is regarded as zero
+pub fn kruskal<S>(n: usize, edges: &[(usize, usize, S)]) -> (S, Vec<usize>)
+where
+    S: Clone + Add<Output = S> + Ord + Default,
+{
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by(|&i, &j| edges[i].2.cmp(&edges[j].2));
+    let mut dsu = Dsu::new(n);
+    let mut total = S::default();
+    let mut chosen = Vec::new();
+    for i in order {
+        let (u, v, ref w) = edges[i];
+        if !dsu.same(u, v) {
+            dsu.merge(u, v);
+            total = total + w.clone();
+            chosen.push(i);
+        }
+    }
+    (total, chosen)
+}
+
+impl<S> UndirectedWeightedListGraph<S>
+where
+    Weighted<S>: Weigh<(usize, S)>,
+    S: Clone + Add<Output = S> + Ord + Default,
+{
+    /// minimum spanning forest by Kruskal's algorithm, returns total weight and edge ids
+    pub fn kruskal(&self) -> (S, Vec<usize>) {
+        kruskal(self.len(), &self.weighted_edges())
+    }
+
+    /// minimum spanning forest by Prim's algorithm, returns total weight and edge ids
+    pub fn prim(&self) -> (S, Vec<usize>) {
+        let n = self.len();
+        let mut used = vec![false; n];
+        let mut total = S::default();
+        let mut chosen = Vec::new();
+        let mut heap = BinaryHeap::new();
+        for root in 0..n {
+            if used[root] {
+                continue;
+            }
+            heap.push(Reverse((S::default(), root, None)));
+            while let Some(Reverse((w, u, id))) = heap.pop() {
+                if used[u] {
+                    continue;
+                }
+                used[u] = true;
+                if let Some(id) = id {
+                    total = total + w;
+                    chosen.push(id);
+                }
+                for ((v, w), &id) in self.inner[u].iter().zip(self.ids[u].iter()) {
+                    if !used[*v] {
+                        heap.push(Reverse((w.clone(), *v, Some(id))));
+                    }
+                }
+            }
+        }
+        (total, chosen)
+    }
+
+    /// minimum spanning forest as a graph on the same vertices
+    pub fn minimum_spanning_forest(&self) -> Self {
+        let edges = self.weighted_edges();
+        let (_, chosen) = kruskal(self.len(), &edges);
+        let mut res = Self::new(self.len());
+        for i in chosen {
+            let (u, v, w) = edges[i].clone();
+            res.add_edge(u, v, w);
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::super::{random::test_rng, UndirectedWeightedListGraph};
+    use super::kruskal;
+
+    #[test]
+    fn mst() {
+        let edges = &[
+            (0, 1, 7),
+            (0, 3, 5),
+            (1, 2, 8),
+            (1, 3, 9),
+            (1, 4, 7),
+            (2, 4, 5),
+            (3, 4, 15),
+            (3, 5, 6),
+            (4, 5, 8),
+            (4, 6, 9),
+            (5, 6, 11),
+        ];
+        assert_eq!(kruskal(7, edges).0, 39);
+
+        let g = UndirectedWeightedListGraph::from_edges(7, edges);
+        let (w, mut ids) = g.kruskal();
+        ids.sort_unstable();
+        assert_eq!(w, 39);
+        assert_eq!(ids, vec![0, 1, 4, 5, 7, 9]);
+
+        let (w, mut ids) = g.prim();
+        ids.sort_unstable();
+        assert_eq!(w, 39);
+        assert_eq!(ids, vec![0, 1, 4, 5, 7, 9]);
+
+        let t = g.minimum_spanning_forest();
+        assert_eq!(t.len(), 7);
+        assert_eq!(t.edge_count(), 6);
+        assert_eq!(t.weighted_edges().iter().map(|e| e.2).sum::<i32>(), 39);
+    }
+
+    #[test]
+    fn forest() {
+        // two components, a self loop and a multi edge
+        let edges = &[(0, 1, 3), (1, 0, 1), (2, 3, 4), (3, 3, -10), (3, 4, 2)];
+        let g = UndirectedWeightedListGraph::from_edges(6, edges);
+        assert_eq!(g.kruskal(), (7, vec![1, 4, 2]));
+        assert_eq!(g.prim().0, 7);
+        assert_eq!(g.minimum_spanning_forest().edge_count(), 3);
+    }
+
+    #[test]
+    fn random_prim_kruskal() {
+        let mut rng = test_rng(42);
+        for _ in 0..50 {
+            let n = rng.gen_range(1, 31);
+            let m = rng.gen_range(0, 100);
+            // not always connected, with self loops and multi edges
+            let edges: Vec<_> = (0..m)
+                .map(|_| {
+                    (
+                        rng.gen_range(0, n),
+                        rng.gen_range(0, n),
+                        rng.gen_range(0, 100i64),
+                    )
+                })
+                .collect();
+            let g = UndirectedWeightedListGraph::from_edges(n, &edges);
+            let (wk, ek) = g.kruskal();
+            let (wp, ep) = g.prim();
+            assert_eq!(wk, wp);
+            assert_eq!(ek.len(), ep.len());
+        }
+    }
+}
@@ -0,0 +1,342 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    io::{self, BufRead},
+    ops::Add,
+    str::FromStr,
+};
+
+use super::{
+    DirectedUnweightedListGraph, DirectedWeightedListGraph, UndirectedUnweightedListGraph,
+    UndirectedWeightedListGraph, Weigh, Weighted,
+};
+
+/// index of the first vertex in the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base {
+    Zero,
+    One,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    Io(io::Error),
+    /// input ended before `expected` was read
+    UnexpectedEof {
+        expected: &'static str,
+    },
+    /// token which cannot be parsed as `expected`
+    InvalidToken {
+        token: String,
+        expected: &'static str,
+    },
+    /// vertex as written in the input, which is not in `[base, base + n)`
+    VertexOutOfRange {
+        vertex: usize,
+        n: usize,
+        base: Base,
+    },
+    /// number of vertices larger than `MAX_VERTICES`
+    TooManyVertices {
+        n: usize,
+    },
+    /// entry `u -> v` of an undirected adjacency list without the mirrored `v -> u`
+    Asymmetric {
+        u: usize,
+        v: usize,
+    },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Io(e) => write!(f, "io error: {}", e),
+            ParseError::UnexpectedEof { expected } => {
+                write!(f, "unexpected end of input, expected {}", expected)
+            }
+            ParseError::InvalidToken { token, expected } => {
+                write!(f, "invalid token {:?}, expected {}", token, expected)
+            }
+            ParseError::VertexOutOfRange { vertex, n, base } => {
+                let first = if *base == Base::One { 1 } else { 0 };
+                write!(
+                    f,
+                    "vertex {} is out of range [{}, {})",
+                    vertex,
+                    first,
+                    first + n
+                )
+            }
+            ParseError::TooManyVertices { n } => {
+                write!(f, "{} vertices exceed the limit {}", n, MAX_VERTICES)
+            }
+            ParseError::Asymmetric { u, v } => {
+                write!(f, "undirected entry {} -> {} has no mirrored entry", u, v)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<io::Error> for ParseError {
+    fn from(e: io::Error) -> Self {
+        ParseError::Io(e)
+    }
+}
+
+type Edges<E> = (usize, Vec<E>);
+
+/// largest number of vertices accepted from the input,
+/// so that a broken header does not exhaust memory before any edge is read
+pub const MAX_VERTICES: usize = 10_000_000;
+
+/// whitespace separated tokens, read from `reader` only as far as needed
+struct Tokens<R> {
+    reader: R,
+}
+
+impl<R: BufRead> Tokens<R> {
+    fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// consume the next token and nothing after it
+    fn token(&mut self) -> Result<Option<String>, ParseError> {
+        let mut token = Vec::new();
+        loop {
+            let buf = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                break;
+            }
+            let mut used = 0;
+            let mut done = false;
+            for &b in buf {
+                if b.is_ascii_whitespace() {
+                    if !token.is_empty() {
+                        done = true;
+                        break;
+                    }
+                } else {
+                    token.push(b);
+                }
+                used += 1;
+            }
+            self.reader.consume(used);
+            if done {
+                break;
+            }
+        }
+        if token.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(String::from_utf8_lossy(&token).into_owned()))
+        }
+    }
+
+    fn next<T: FromStr>(&mut self, expected: &'static str) -> Result<T, ParseError> {
+        let token = self
+            .token()?
+            .ok_or(ParseError::UnexpectedEof { expected })?;
+        token
+            .parse()
+            .map_err(|_| ParseError::InvalidToken { token, expected })
+    }
+
+    fn vertex_count(&mut self) -> Result<usize, ParseError> {
+        let n = self.next("number of vertices")?;
+        if n > MAX_VERTICES {
+            return Err(ParseError::TooManyVertices { n });
+        }
+        Ok(n)
+    }
+
+    fn vertex(&mut self, n: usize, base: Base) -> Result<usize, ParseError> {
+        let vertex: usize = self.next("vertex")?;
+        let first = if base == Base::One { 1 } else { 0 };
+        if vertex < first || vertex - first >= n {
+            return Err(ParseError::VertexOutOfRange { vertex, n, base });
+        }
+        Ok(vertex - first)
+    }
+
+    /// `n m` and `m` lines of `u v`
+    fn edges(&mut self, base: Base) -> Result<Edges<(usize, usize)>, ParseError> {
+        let n = self.vertex_count()?;
+        let m: usize = self.next("number of edges")?;
+        // `m` is not trusted for preallocation
+        let mut edges = Vec::new();
+        for _ in 0..m {
+            let u = self.vertex(n, base)?;
+            let v = self.vertex(n, base)?;
+            edges.push((u, v));
+        }
+        Ok((n, edges))
+    }
+
+    /// `n m` and `m` lines of `u v w`
+    fn weighted_edges<S: FromStr>(
+        &mut self,
+        base: Base,
+    ) -> Result<Edges<(usize, usize, S)>, ParseError> {
+        let n = self.vertex_count()?;
+        let m: usize = self.next("number of edges")?;
+        // `m` is not trusted for preallocation
+        let mut edges = Vec::new();
+        for _ in 0..m {
+            let u = self.vertex(n, base)?;
+            let v = self.vertex(n, base)?;
+            let w = self.next("weight")?;
+            edges.push((u, v, w));
+        }
+        Ok((n, edges))
+    }
+
+    /// `n` and `p_2 ... p_n`, returns `(p_i, i)`
+    fn parents(&mut self, base: Base) -> Result<Edges<(usize, usize)>, ParseError> {
+        let n = self.vertex_count()?;
+        let mut edges = Vec::new();
+        for v in 1..n {
+            edges.push((self.vertex(n, base)?, v));
+        }
+        Ok((n, edges))
+    }
+}
+
+impl UndirectedUnweightedListGraph {
+    /// read `n m` and `m` edges `u v`
+    ///
+    /// only the graph is consumed; pass `&mut reader` to keep reading after it
+    ///
+    /// ```ignore
+    /// let mut stdin = stdin.lock();
+    /// let g = UndirectedUnweightedListGraph::parse(&mut stdin, Base::One)?;
+    /// ```
+    pub fn parse<R: BufRead>(reader: R, base: Base) -> Result<Self, ParseError> {
+        let (n, edges) = Tokens::new(reader).edges(base)?;
+        Ok(Self::from_edges(n, &edges))
+    }
+
+    /// read `n` and parents `p_2 ... p_n` of a tree rooted at the first vertex
+    pub fn parse_parents<R: BufRead>(reader: R, base: Base) -> Result<Self, ParseError> {
+        let (n, edges) = Tokens::new(reader).parents(base)?;
+        Ok(Self::from_edges(n, &edges))
+    }
+}
+
+impl DirectedUnweightedListGraph {
+    /// read `n m` and `m` edges `u v`
+    pub fn parse<R: BufRead>(reader: R, base: Base) -> Result<Self, ParseError> {
+        let (n, edges) = Tokens::new(reader).edges(base)?;
+        Ok(Self::from_edges(n, &edges))
+    }
+
+    /// read `n` and parents `p_2 ... p_n` of a tree rooted at the first vertex,
+    /// edges are directed from parents to children
+    pub fn parse_parents<R: BufRead>(reader: R, base: Base) -> Result<Self, ParseError> {
+        let (n, edges) = Tokens::new(reader).parents(base)?;
+        Ok(Self::from_edges(n, &edges))
+    }
+}
+
+impl<S> UndirectedWeightedListGraph<S>
+where
+    Weighted<S>: Weigh<(usize, S)>,
+    S: Clone + Add + Ord + FromStr,
+{
+    /// read `n m` and `m` edges `u v w`
+    pub fn parse<R: BufRead>(reader: R, base: Base) -> Result<Self, ParseError> {
+        let (n, edges) = Tokens::new(reader).weighted_edges(base)?;
+        Ok(Self::from_edges(n, &edges))
+    }
+}
+
+impl<S> DirectedWeightedListGraph<S>
+where
+    Weighted<S>: Weigh<(usize, S)>,
+    S: Clone + Add + Ord + FromStr,
+{
+    /// read `n m` and `m` edges `u v w`
+    pub fn parse<R: BufRead>(reader: R, base: Base) -> Result<Self, ParseError> {
+        let (n, edges) = Tokens::new(reader).weighted_edges(base)?;
+        Ok(Self::from_edges(n, &edges))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        DirectedUnweightedListGraph, DirectedWeightedListGraph, UndirectedUnweightedListGraph,
+    };
+    use super::{Base, ParseError};
+
+    #[test]
+    fn edges() {
+        let input = "4 3\n1 2\n2 3\n4 1\n";
+        let g = UndirectedUnweightedListGraph::parse(input.as_bytes(), Base::One).unwrap();
+        assert_eq!(g.len(), 4);
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(g.edge(2), (3, 0));
+
+        let g = DirectedUnweightedListGraph::parse("3 2 0 1 1 2".as_bytes(), Base::Zero).unwrap();
+        assert_eq!(g.edge(1), (1, 2));
+
+        let g =
+            DirectedWeightedListGraph::<i64>::parse("2 1\n1 2 -5\n".as_bytes(), Base::One).unwrap();
+        assert_eq!(g.weighted_edges(), vec![(0, 1, -5)]);
+    }
+
+    #[test]
+    fn rest_of_input() {
+        let mut input = "3 2\n1 2\n2 3 2\n1 3\n".as_bytes();
+        let g = UndirectedUnweightedListGraph::parse(&mut input, Base::One).unwrap();
+        assert_eq!(g.edge_count(), 2);
+        assert_eq!(input, &b" 2\n1 3\n"[..]);
+    }
+
+    #[test]
+    fn huge_count() {
+        let input = "2 18446744073709551615\n1 2\n";
+        let err = UndirectedUnweightedListGraph::parse(input.as_bytes(), Base::One);
+        assert!(matches!(err, Err(ParseError::UnexpectedEof { .. })));
+        let err = UndirectedUnweightedListGraph::parse("99999999999999 0".as_bytes(), Base::One);
+        assert!(matches!(
+            err,
+            Err(ParseError::TooManyVertices { n: 99999999999999 })
+        ));
+        let err = DirectedUnweightedListGraph::parse_parents("10000001\n".as_bytes(), Base::One);
+        assert!(matches!(err, Err(ParseError::TooManyVertices { .. })));
+    }
+
+    #[test]
+    fn parents() {
+        let g = UndirectedUnweightedListGraph::parse_parents("5\n1 1 2 2\n".as_bytes(), Base::One)
+            .unwrap();
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.edge(3), (1, 4));
+        let g = DirectedUnweightedListGraph::parse_parents("1\n".as_bytes(), Base::Zero).unwrap();
+        assert_eq!((g.len(), g.edge_count()), (1, 0));
+    }
+
+    #[test]
+    fn errors() {
+        let err = UndirectedUnweightedListGraph::parse("3 1\n0 2".as_bytes(), Base::One);
+        assert!(matches!(
+            err,
+            Err(ParseError::VertexOutOfRange {
+                vertex: 0,
+                n: 3,
+                ..
+            })
+        ));
+        let err = UndirectedUnweightedListGraph::parse("3 1\n1 3".as_bytes(), Base::Zero);
+        assert_eq!(
+            err.err().unwrap().to_string(),
+            "vertex 3 is out of range [0, 3)"
+        );
+        let err = UndirectedUnweightedListGraph::parse("3 2\n1 2".as_bytes(), Base::One);
+        assert!(matches!(err, Err(ParseError::UnexpectedEof { .. })));
+        let err = DirectedWeightedListGraph::<u32>::parse("2 1\n1 2 x".as_bytes(), Base::One);
+        assert!(matches!(err, Err(ParseError::InvalidToken { .. })));
+    }
+}
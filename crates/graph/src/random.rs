@@ -0,0 +1,327 @@
+//! random graphs for stress tests, enabled by the `random` feature
+//!
+//! every generator takes `rng`, so the result is reproducible with a seeded one
+//!
+//! ```
+//! use rand::{rngs::StdRng, SeedableRng};
+//! let mut rng = StdRng::seed_from_u64(42);
+//! let g = graph::random::tree(&mut rng, 100);
+//! ```
+
+use std::collections::HashSet;
+
+use rand::{distributions::uniform::SampleUniform, seq::SliceRandom, Rng};
+
+use super::{
+    Direct, DirectedUnweightedListGraph, UndirectedUnweightedListGraph, UnweightedListGraph, Weigh,
+    Weighted, WeightedListGraph,
+};
+
+/// random permutation of `0..n`
+fn permutation<R: Rng>(rng: &mut R, n: usize) -> Vec<usize> {
+    let mut p: Vec<usize> = (0..n).collect();
+    p.shuffle(rng);
+    p
+}
+
+/// `m` distinct edges among `total` candidates,
+/// by rejection if sparse, otherwise by shuffling all of them
+fn distinct_edges<R, F, A>(
+    rng: &mut R,
+    total: usize,
+    m: usize,
+    mut sample: F,
+    all: A,
+) -> Vec<(usize, usize)>
+where
+    R: Rng,
+    F: FnMut(&mut R) -> (usize, usize),
+    A: FnOnce() -> Vec<(usize, usize)>,
+{
+    assert!(m <= total, "too many edges");
+    if m * 2 <= total {
+        let mut seen = HashSet::with_capacity(m);
+        let mut res = Vec::with_capacity(m);
+        while res.len() < m {
+            let e = sample(rng);
+            if seen.insert(e) {
+                res.push(e);
+            }
+        }
+        res
+    } else {
+        let mut all = all();
+        all.shuffle(rng);
+        all.truncate(m);
+        all
+    }
+}
+
+/// uniformly random labeled tree, by decoding a random Prüfer sequence
+pub fn tree<R: Rng>(rng: &mut R, n: usize) -> UndirectedUnweightedListGraph {
+    if n <= 2 {
+        return UndirectedUnweightedListGraph::from_edges(n, if n == 2 { &[(0, 1)] } else { &[] });
+    }
+    let prufer: Vec<usize> = (0..n - 2).map(|_| rng.gen_range(0, n)).collect();
+    let mut degree = vec![1; n];
+    for &v in &prufer {
+        degree[v] += 1;
+    }
+    let mut edges = Vec::with_capacity(n - 1);
+    // smallest leaf is `leaf`, found by a pointer which only moves forward
+    let mut ptr = (0..n).find(|&v| degree[v] == 1).unwrap();
+    let mut leaf = ptr;
+    for &v in &prufer {
+        edges.push((leaf, v));
+        degree[v] -= 1;
+        if degree[v] == 1 && v < ptr {
+            leaf = v;
+        } else {
+            ptr += 1;
+            while degree[ptr] != 1 {
+                ptr += 1;
+            }
+            leaf = ptr;
+        }
+    }
+    edges.push((leaf, n - 1));
+    UndirectedUnweightedListGraph::from_edges(n, &edges)
+}
+
+/// path through all vertices in random order
+pub fn path<R: Rng>(rng: &mut R, n: usize) -> UndirectedUnweightedListGraph {
+    let p = permutation(rng, n);
+    let edges: Vec<_> = p.windows(2).map(|w| (w[0], w[1])).collect();
+    UndirectedUnweightedListGraph::from_edges(n, &edges)
+}
+
+/// star whose center is random
+pub fn star<R: Rng>(rng: &mut R, n: usize) -> UndirectedUnweightedListGraph {
+    let mut g = UndirectedUnweightedListGraph::new(n);
+    if n > 0 {
+        let center = rng.gen_range(0, n);
+        for v in (0..n).filter(|&v| v != center) {
+            g.add_edge(center, v);
+        }
+    }
+    g
+}
+
+/// path of `spine` vertices, and the other vertices attached to random vertices on it
+pub fn caterpillar<R: Rng>(rng: &mut R, n: usize, spine: usize) -> UndirectedUnweightedListGraph {
+    assert!(0 < spine && spine <= n, "spine must be in [1, n]");
+    let p = permutation(rng, n);
+    let mut g = UndirectedUnweightedListGraph::new(n);
+    for i in 1..n {
+        let parent = if i < spine {
+            i - 1
+        } else {
+            rng.gen_range(0, spine)
+        };
+        g.add_edge(p[parent], p[i]);
+    }
+    g
+}
+
+/// connected simple graph with `m` edges, `n - 1 <= m <= n(n - 1) / 2`
+pub fn connected<R: Rng>(rng: &mut R, n: usize, m: usize) -> UndirectedUnweightedListGraph {
+    assert!(n > 0 && n - 1 <= m, "too few edges to be connected");
+    let mut g = tree(rng, n);
+    let used: HashSet<(usize, usize)> = (0..n - 1)
+        .map(|id| {
+            let (u, v) = g.edge(id);
+            (u.min(v), u.max(v))
+        })
+        .collect();
+    let extra = distinct_edges(
+        rng,
+        n * (n - 1) / 2 - (n - 1),
+        m - (n - 1),
+        |rng| loop {
+            let u = rng.gen_range(0, n);
+            let v = rng.gen_range(0, n);
+            let e = (u.min(v), u.max(v));
+            if u != v && !used.contains(&e) {
+                return e;
+            }
+        },
+        || {
+            (0..n)
+                .flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+                .filter(|e| !used.contains(e))
+                .collect()
+        },
+    );
+    for (u, v) in extra {
+        g.add_edge(u, v);
+    }
+    g
+}
+
+/// DAG with `m` distinct edges, whose topological order is random
+pub fn dag<R: Rng>(rng: &mut R, n: usize, m: usize) -> DirectedUnweightedListGraph {
+    let p = permutation(rng, n);
+    let edges = distinct_edges(
+        rng,
+        n * n.saturating_sub(1) / 2,
+        m,
+        |rng| loop {
+            let i = rng.gen_range(0, n);
+            let j = rng.gen_range(0, n);
+            if i < j {
+                return (i, j);
+            }
+        },
+        || {
+            (0..n)
+                .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+                .collect()
+        },
+    );
+    let edges: Vec<_> = edges.into_iter().map(|(i, j)| (p[i], p[j])).collect();
+    DirectedUnweightedListGraph::from_edges(n, &edges)
+}
+
+/// bipartite graph with `m` distinct edges between `0..left` and `left..left + right`
+pub fn bipartite<R: Rng>(
+    rng: &mut R,
+    left: usize,
+    right: usize,
+    m: usize,
+) -> UndirectedUnweightedListGraph {
+    let edges = distinct_edges(
+        rng,
+        left * right,
+        m,
+        |rng| (rng.gen_range(0, left), left + rng.gen_range(0, right)),
+        || {
+            (0..left)
+                .flat_map(|u| (left..left + right).map(move |v| (u, v)))
+                .collect()
+        },
+    );
+    UndirectedUnweightedListGraph::from_edges(left + right, &edges)
+}
+
+/// same graph as `g` whose edges have weights uniformly chosen from `[low, high)`,
+/// ids of edges are kept
+pub fn with_weights<R, S, D>(
+    rng: &mut R,
+    g: &UnweightedListGraph<D>,
+    low: S,
+    high: S,
+) -> WeightedListGraph<S, D>
+where
+    R: Rng,
+    S: Clone + std::ops::Add + Ord + SampleUniform,
+    D: Direct,
+    Weighted<S>: Weigh<(usize, S)>,
+{
+    let mut res = WeightedListGraph::new(g.len());
+    for &(u, v) in g.ends.iter() {
+        let w = rng.gen_range(low.clone(), high.clone());
+        res.inner[u].push((v, w.clone()));
+        if !D::DIRECTED {
+            res.inner[v].push((u, w));
+        }
+        res.push_edge(u, v);
+    }
+    res
+}
+
+/// seeded rng shared by the tests of this crate
+#[cfg(test)]
+pub(crate) fn test_rng(seed: u64) -> rand::rngs::StdRng {
+    use rand::SeedableRng;
+    rand::rngs::StdRng::seed_from_u64(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_rng;
+
+    use super::super::{bfs::Bfsable as _, UndirectedUnweightedListGraph};
+
+    fn is_tree(g: &UndirectedUnweightedListGraph) -> bool {
+        g.edge_count() + 1 == g.len() && g.bfs(0).distances().iter().all(|d| d.is_some())
+    }
+
+    #[test]
+    fn trees() {
+        let mut rng = test_rng(0);
+        for n in 1..30 {
+            assert!(is_tree(&super::tree(&mut rng, n)));
+            assert!(is_tree(&super::path(&mut rng, n)));
+            assert!(is_tree(&super::star(&mut rng, n)));
+            assert!(is_tree(&super::caterpillar(&mut rng, n, n - n / 2)));
+        }
+        let g = super::path(&mut rng, 10);
+        assert!((0..10).all(|v| g.neighbors(v).count() <= 2));
+    }
+
+    #[test]
+    fn prufer_is_uniform() {
+        // 4^2 = 16 labeled trees on 4 vertices
+        let mut rng = test_rng(1);
+        let mut count = std::collections::HashMap::new();
+        for _ in 0..16000 {
+            let g = super::tree(&mut rng, 4);
+            let mut edges: Vec<_> = (0..3)
+                .map(|id| {
+                    let (u, v) = g.edge(id);
+                    (u.min(v), u.max(v))
+                })
+                .collect();
+            edges.sort_unstable();
+            *count.entry(edges).or_insert(0) += 1;
+        }
+        assert_eq!(count.len(), 16);
+        assert!(count.values().all(|&c| 800 < c && c < 1200));
+    }
+
+    #[test]
+    fn graphs() {
+        let mut rng = test_rng(2);
+        for &(n, m) in &[(1, 0), (10, 9), (10, 20), (10, 45), (50, 300)] {
+            let g = super::connected(&mut rng, n, m);
+            assert_eq!(g.edge_count(), m);
+            assert!(g.bfs(0).distances().iter().all(|d| d.is_some()));
+            let mut edges: Vec<_> = (0..m)
+                .map(|id| {
+                    let (u, v) = g.edge(id);
+                    assert_ne!(u, v);
+                    (u.min(v), u.max(v))
+                })
+                .collect();
+            edges.sort_unstable();
+            edges.dedup();
+            assert_eq!(edges.len(), m);
+        }
+
+        let g = super::dag(&mut rng, 20, 150);
+        assert_eq!(g.edge_count(), 150);
+        assert!(g.topological_sort().is_ok());
+
+        let g = super::bipartite(&mut rng, 5, 7, 30);
+        assert_eq!(g.edge_count(), 30);
+        assert!(g.bipartition().is_ok());
+        assert!((0..30).all(|id| {
+            let (u, v) = g.edge(id);
+            u < 5 && (5..12).contains(&v)
+        }));
+
+        let w = super::with_weights(&mut rng, &g, -10i64, 10);
+        let edges = w.weighted_edges();
+        assert!(edges
+            .iter()
+            .enumerate()
+            .all(|(id, &(u, v, c))| (u, v) == g.edge(id) && (-10..10).contains(&c)));
+    }
+
+    #[test]
+    fn reproducible() {
+        let a = super::connected(&mut test_rng(3), 30, 60);
+        let b = super::connected(&mut test_rng(3), 30, 60);
+        assert_eq!(a.to_json(), b.to_json());
+    }
+}
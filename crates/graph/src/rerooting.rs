@@ -0,0 +1,112 @@
+use super::{ListGraph, Undirected, Weigh};
+
+impl<S, W: Weigh<S>> ListGraph<S, Undirected, W> {
+    /// DP on the tree for every root in `O(n)`
+    ///
+    /// `merge` and `identity` form a commutative monoid over the values of subtrees,
+    /// `add_root(x, v)` turns `x`, the merged values of the children of `v`, into the value of the subtree of `v`,
+    /// returns the value of the whole tree rooted at each vertex
+    ///
+    /// ```ignore
+    /// // sum of distances to all vertices, as (size, sum)
+    /// let ans = g.rerooting((0, 0), |a, b| (a.0 + b.0, a.1 + b.1), |x, _| (x.0 + 1, x.1 + x.0));
+    /// ```
+    pub fn rerooting<T, M, A>(&self, identity: T, merge: M, add_root: A) -> Vec<T>
+    where
+        T: Clone,
+        M: Fn(&T, &T) -> T,
+        A: Fn(&T, usize) -> T,
+    {
+        let n = self.inner.len();
+        if n == 0 {
+            return Vec::new();
+        }
+        assert_eq!(self.edge_count() + 1, n, "not a tree");
+
+        let mut parent = vec![usize::MAX; n];
+        let mut order = Vec::with_capacity(n);
+        let mut visited = vec![false; n];
+        visited[0] = true;
+        order.push(0);
+        let mut i = 0;
+        while i < order.len() {
+            let u = order[i];
+            i += 1;
+            for v in self.neighbors(u) {
+                if !visited[v] {
+                    visited[v] = true;
+                    parent[v] = u;
+                    order.push(v);
+                }
+            }
+        }
+        assert_eq!(order.len(), n, "not connected");
+
+        let children: Vec<Vec<usize>> = (0..n)
+            .map(|u| self.neighbors(u).filter(|&v| parent[v] == u).collect())
+            .collect();
+
+        // value of the subtree of each vertex when rooted at 0
+        let mut down = vec![identity.clone(); n];
+        for &u in order.iter().rev() {
+            let acc = children[u]
+                .iter()
+                .fold(identity.clone(), |acc, &c| merge(&acc, &down[c]));
+            down[u] = add_root(&acc, u);
+        }
+
+        // value of the part above each vertex, seen as a subtree of its parent
+        let mut up = vec![identity.clone(); n];
+        let mut res = vec![identity.clone(); n];
+        let mut suffix = Vec::new();
+        for &u in order.iter() {
+            let ch = &children[u];
+            suffix.clear();
+            suffix.resize(ch.len() + 1, identity.clone());
+            for j in (0..ch.len()).rev() {
+                suffix[j] = merge(&down[ch[j]], &suffix[j + 1]);
+            }
+            let mut prefix = up[u].clone();
+            res[u] = add_root(&merge(&prefix, &suffix[0]), u);
+            for (j, &c) in ch.iter().enumerate() {
+                up[c] = add_root(&merge(&prefix, &suffix[j + 1]), u);
+                prefix = merge(&prefix, &down[c]);
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        bfs::Bfsable as _,
+        random::{self, test_rng},
+        UndirectedUnweightedListGraph,
+    };
+
+    #[test]
+    fn sum_of_distances() {
+        let n = 100;
+        let g = random::tree(&mut test_rng(7), n);
+        let ans = g.rerooting(
+            (0usize, 0usize),
+            |a, b| (a.0 + b.0, a.1 + b.1),
+            |x, _| (x.0 + 1, x.1 + x.0),
+        );
+        for (v, &(size, sum)) in ans.iter().enumerate() {
+            assert_eq!(size, n);
+            let expected: usize = g.bfs(v).distances().iter().map(|d| d.unwrap()).sum();
+            assert_eq!(sum, expected);
+        }
+    }
+
+    #[test]
+    fn eccentricity() {
+        // height of the tree rooted at each vertex, -1 for empty
+        let g =
+            UndirectedUnweightedListGraph::from_edges(6, &[(0, 1), (1, 2), (2, 3), (1, 4), (4, 5)]);
+        let ans = g.rerooting(-1i64, |a, b| *a.max(b), |x, _| x + 1);
+        assert_eq!(ans, vec![3, 2, 3, 4, 3, 4]);
+    }
+}
@@ -0,0 +1,130 @@
+use std::cmp::min;
+
+use super::DirectedUnweightedListGraph;
+
+impl DirectedUnweightedListGraph {
+    /// strongly connected components by Tarjan's algorithm
+    ///
+    /// returns `(number of components, id of component for each vertex)`,
+    /// where ids are in topological order: for each edge `u -> v`, `id[u] <= id[v]`
+    pub fn scc_ids(&self) -> (usize, Vec<usize>) {
+        let n = self.len();
+        const UNSEEN: usize = usize::MAX;
+        // vertices whose component is determined have `ord == n`
+        let mut ord = vec![UNSEEN; n];
+        let mut low = vec![0; n];
+        let mut ids = vec![0; n];
+        let mut visited = Vec::with_capacity(n);
+        let mut call = Vec::new();
+        let mut now = 0;
+        let mut group = 0;
+        for root in 0..n {
+            if ord[root] != UNSEEN {
+                continue;
+            }
+            ord[root] = now;
+            low[root] = now;
+            now += 1;
+            visited.push(root);
+            call.push((root, 0));
+            while let Some((v, i)) = call.last_mut() {
+                let v = *v;
+                if let Some(&to) = self.inner[v].get(*i) {
+                    *i += 1;
+                    if ord[to] == UNSEEN {
+                        ord[to] = now;
+                        low[to] = now;
+                        now += 1;
+                        visited.push(to);
+                        call.push((to, 0));
+                    } else {
+                        low[v] = min(low[v], ord[to]);
+                    }
+                } else {
+                    call.pop();
+                    if let Some(&(p, _)) = call.last() {
+                        low[p] = min(low[p], low[v]);
+                    }
+                    if low[v] == ord[v] {
+                        while let Some(u) = visited.pop() {
+                            ord[u] = n;
+                            ids[u] = group;
+                            if u == v {
+                                break;
+                            }
+                        }
+                        group += 1;
+                    }
+                }
+            }
+        }
+        // Tarjan's algorithm finds components in reverse topological order
+        for id in ids.iter_mut() {
+            *id = group - 1 - *id;
+        }
+        (group, ids)
+    }
+
+    /// vertices of each strongly connected component, in topological order
+    pub fn scc(&self) -> Vec<Vec<usize>> {
+        let (k, ids) = self.scc_ids();
+        let mut groups = vec![Vec::new(); k];
+        for (v, &id) in ids.iter().enumerate() {
+            groups[id].push(v);
+        }
+        groups
+    }
+
+    /// ids of components and the DAG whose vertices are the components
+    ///
+    /// multiple edges between two components are merged into one
+    pub fn condensation(&self) -> (Vec<usize>, DirectedUnweightedListGraph) {
+        let (k, ids) = self.scc_ids();
+        let mut edges = Vec::new();
+        for (u, adj) in self.inner.iter().enumerate() {
+            for &v in adj.iter() {
+                if ids[u] != ids[v] {
+                    edges.push((ids[u], ids[v]));
+                }
+            }
+        }
+        edges.sort_unstable();
+        edges.dedup();
+        let dag = DirectedUnweightedListGraph::from_edges(k, &edges);
+        (ids, dag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::DirectedUnweightedListGraph;
+
+    #[test]
+    fn scc() {
+        let edges = &[(1, 4), (5, 2), (3, 0), (5, 5), (4, 1), (0, 3), (4, 2)];
+        let g = DirectedUnweightedListGraph::from_edges(6, edges);
+        let groups = g.scc();
+        assert_eq!(groups.len(), 4);
+        let (_, ids) = g.scc_ids();
+        assert!(edges.iter().all(|&(u, v)| ids[u] <= ids[v]));
+        assert_eq!(ids[1], ids[4]);
+        assert_eq!(ids[0], ids[3]);
+        assert!(ids[5] < ids[2]);
+        assert!(ids[4] < ids[2]);
+
+        let (cids, dag) = g.condensation();
+        assert_eq!(cids, ids);
+        assert_eq!(dag.len(), 4);
+        assert!(dag.topological_sort().is_ok());
+        assert_eq!(dag.inner[ids[4]], vec![ids[2]]);
+        assert_eq!(dag.inner[ids[5]], vec![ids[2]]);
+    }
+
+    #[test]
+    fn long_cycle() {
+        let n = 1_000_000;
+        let edges: Vec<_> = (0..n).map(|i| (i, (i + 1) % n)).collect();
+        let g = DirectedUnweightedListGraph::from_edges(n, &edges);
+        assert_eq!(g.scc_ids().0, 1);
+    }
+}
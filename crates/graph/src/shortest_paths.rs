@@ -0,0 +1,403 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    ops::{Add, Sub},
+};
+
+use super::{
+    dag::Cycle, dijkstra::is_shorter, Direct, DirectedUnweightedListGraph, Weigh, Weighted,
+    WeightedListGraph,
+};
+
+const NIL: usize = usize::MAX;
+
+/// node of persistent leftist heap of sidetrack arcs
+struct Node<S> {
+    key: S,
+    arc: usize,
+    left: usize,
+    right: usize,
+    rank: usize,
+}
+
+struct LeftistHeap<S> {
+    nodes: Vec<Node<S>>,
+}
+
+impl<S: Clone + Ord> LeftistHeap<S> {
+    fn rank(&self, a: usize) -> usize {
+        if a == NIL {
+            0
+        } else {
+            self.nodes[a].rank
+        }
+    }
+
+    fn singleton(&mut self, key: S, arc: usize) -> usize {
+        self.nodes.push(Node {
+            key,
+            arc,
+            left: NIL,
+            right: NIL,
+            rank: 1,
+        });
+        self.nodes.len() - 1
+    }
+
+    /// merge without modifying `a` and `b`, recursion is as deep as the right spines
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL {
+            return b;
+        }
+        if b == NIL {
+            return a;
+        }
+        let (a, b) = if self.nodes[a].key <= self.nodes[b].key {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let right = self.merge(self.nodes[a].right, b);
+        let left = self.nodes[a].left;
+        let (left, right) = if self.rank(left) >= self.rank(right) {
+            (left, right)
+        } else {
+            (right, left)
+        };
+        let rank = self.rank(right) + 1;
+        self.nodes.push(Node {
+            key: self.nodes[a].key.clone(),
+            arc: self.nodes[a].arc,
+            left,
+            right,
+            rank,
+        });
+        self.nodes.len() - 1
+    }
+}
+
+impl<S, D> WeightedListGraph<S, D>
+where
+    Weighted<S>: Weigh<(usize, S)>,
+    S: Clone + Add<Output = S> + Ord + Default,
+    D: Direct,
+{
+    /// `(from, to, weight, id)`, both directions of each edge if undirected
+    fn arcs(&self) -> Vec<(usize, usize, S, usize)> {
+        let mut arcs = Vec::new();
+        for (id, (u, v, w)) in self.weighted_edges().into_iter().enumerate() {
+            if !D::DIRECTED {
+                arcs.push((v, u, w.clone(), id));
+            }
+            arcs.push((u, v, w, id));
+        }
+        arcs
+    }
+
+    /// graph of edges `u -> v` with `dist[u] + w = dist[v]` from `start`,
+    /// every shortest path from `start` is a path on it
+    ///
+    /// weights must be non-negative, `S::default()` is regarded as zero
+    pub fn shortest_path_dag(&self, start: usize) -> DirectedUnweightedListGraph {
+        let dist = self.dijkstra(start).dist().to_vec();
+        let mut dag = DirectedUnweightedListGraph::new(self.len());
+        for (u, v, w, _) in self.arcs() {
+            if let (Some(du), Some(dv)) = (&dist[u], &dist[v]) {
+                if du.clone() + w == *dv {
+                    dag.add_edge(u, v);
+                }
+            }
+        }
+        dag
+    }
+
+    /// number of shortest paths from `start` to each vertex, modulo `modulo`
+    ///
+    /// fails if there are infinitely many because of a reachable cycle of weight zero
+    pub fn count_shortest_paths(&self, start: usize, modulo: u64) -> Result<Vec<u64>, Cycle> {
+        self.shortest_path_dag(start).count_paths(start, modulo)
+    }
+
+    /// ids of edges which lie on at least one shortest path from `s` to `t`
+    pub fn edges_on_shortest_paths(&self, s: usize, t: usize) -> Vec<usize> {
+        let from_s = self.dijkstra(s).dist().to_vec();
+        let to_t = self.dist_to(t);
+        let total = match &from_s[t] {
+            Some(d) => d.clone(),
+            None => return Vec::new(),
+        };
+        let mut res: Vec<usize> = self
+            .arcs()
+            .into_iter()
+            .filter(|(u, v, w, _)| match (&from_s[*u], &to_t[*v]) {
+                (Some(a), Some(b)) => a.clone() + w.clone() + b.clone() == total,
+                _ => false,
+            })
+            .map(|(_, _, _, id)| id)
+            .collect();
+        res.dedup();
+        res
+    }
+
+    /// distance to `t` from each vertex, and the arc to go next on a shortest path
+    fn dijkstra_to(
+        &self,
+        t: usize,
+        arcs: &[(usize, usize, S, usize)],
+    ) -> (Vec<Option<S>>, Vec<usize>) {
+        let n = self.len();
+        let mut rev = vec![Vec::new(); n];
+        for (i, (_, v, _, _)) in arcs.iter().enumerate() {
+            rev[*v].push(i);
+        }
+        let mut dist: Vec<Option<S>> = vec![None; n];
+        let mut next = vec![NIL; n];
+        let mut heap = BinaryHeap::new();
+        dist[t] = Some(S::default());
+        heap.push(Reverse((S::default(), t)));
+        while let Some(Reverse((d, v))) = heap.pop() {
+            if dist[v].as_ref() != Some(&d) {
+                continue;
+            }
+            for &i in rev[v].iter() {
+                let (u, _, ref w, _) = arcs[i];
+                let cand = d.clone() + w.clone();
+                if is_shorter(&cand, &dist[u]) {
+                    dist[u] = Some(cand.clone());
+                    next[u] = i;
+                    heap.push(Reverse((cand, u)));
+                }
+            }
+        }
+        (dist, next)
+    }
+
+    fn dist_to(&self, t: usize) -> Vec<Option<S>> {
+        self.dijkstra_to(t, &self.arcs()).0
+    }
+}
+
+impl<S, D> WeightedListGraph<S, D>
+where
+    Weighted<S>: Weigh<(usize, S)>,
+    S: Clone + Add<Output = S> + Sub<Output = S> + Ord + Default,
+    D: Direct,
+{
+    /// lengths and vertices of the `k` shortest walks from `s` to `t` by Eppstein's algorithm,
+    /// walks may visit a vertex more than once
+    ///
+    /// fewer than `k` walks are returned if there are not so many,
+    /// `O(m log m + k log k)` besides the output,
+    /// weights must be non-negative and `S::default()` is regarded as zero
+    pub fn k_shortest_walks(&self, s: usize, t: usize, k: usize) -> Vec<(S, Vec<usize>)> {
+        let n = self.len();
+        let arcs = self.arcs();
+        let (dist, next) = self.dijkstra_to(t, &arcs);
+        let mut res = Vec::new();
+        let first = match &dist[s] {
+            Some(d) => d.clone(),
+            None => return res,
+        };
+
+        // heap[v] holds sidetracks of every vertex on the tree path from v to t
+        let mut children = vec![Vec::new(); n];
+        for v in 0..n {
+            if next[v] != NIL {
+                children[arcs[next[v]].1].push(v);
+            }
+        }
+        let mut out = vec![Vec::new(); n];
+        for (i, (u, v, w, _)) in arcs.iter().enumerate() {
+            if let (Some(du), Some(dv)) = (&dist[*u], &dist[*v]) {
+                if next[*u] != i {
+                    out[*u].push((dv.clone() + w.clone() - du.clone(), i));
+                }
+            }
+        }
+        let mut lh = LeftistHeap { nodes: Vec::new() };
+        let mut heap = vec![NIL; n];
+        let mut queue = VecDeque::new();
+        queue.push_back(t);
+        while let Some(v) = queue.pop_front() {
+            let mut h = if v == t { NIL } else { heap[arcs[next[v]].1] };
+            for (key, i) in out[v].drain(..) {
+                let single = lh.singleton(key, i);
+                h = lh.merge(h, single);
+            }
+            heap[v] = h;
+            queue.extend(children[v].iter().copied());
+        }
+
+        // sidetrack sequences as linked lists of (arc, previous)
+        let mut seqs: Vec<(usize, usize)> = Vec::new();
+        let restore = |seq: usize, seqs: &[(usize, usize)]| {
+            let mut sidetracks = Vec::new();
+            let mut cur = seq;
+            while cur != NIL {
+                sidetracks.push(seqs[cur].0);
+                cur = seqs[cur].1;
+            }
+            let mut walk = vec![s];
+            let mut u = s;
+            for &i in sidetracks.iter().rev() {
+                while u != arcs[i].0 {
+                    u = arcs[next[u]].1;
+                    walk.push(u);
+                }
+                u = arcs[i].1;
+                walk.push(u);
+            }
+            while u != t {
+                u = arcs[next[u]].1;
+                walk.push(u);
+            }
+            walk
+        };
+
+        if k == 0 {
+            return res;
+        }
+        res.push((first.clone(), restore(NIL, &seqs)));
+        // (length, heap node, sidetracks before the node)
+        let mut pq = BinaryHeap::new();
+        if heap[s] != NIL {
+            pq.push(Reverse((
+                first + lh.nodes[heap[s]].key.clone(),
+                heap[s],
+                NIL,
+            )));
+        }
+        while res.len() < k {
+            let Reverse((len, node, prefix)) = match pq.pop() {
+                Some(e) => e,
+                None => break,
+            };
+            seqs.push((lh.nodes[node].arc, prefix));
+            let seq = seqs.len() - 1;
+            res.push((len.clone(), restore(seq, &seqs)));
+
+            let Node {
+                ref key,
+                arc,
+                left,
+                right,
+                ..
+            } = lh.nodes[node];
+            for &child in &[left, right] {
+                if child != NIL {
+                    let cand = len.clone() - key.clone() + lh.nodes[child].key.clone();
+                    pq.push(Reverse((cand, child, prefix)));
+                }
+            }
+            let h = heap[arcs[arc].1];
+            if h != NIL {
+                pq.push(Reverse((len + lh.nodes[h].key.clone(), h, seq)));
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cmp::Reverse, collections::BinaryHeap};
+
+    use rand::Rng;
+
+    use super::super::{random::test_rng, DirectedWeightedListGraph, UndirectedWeightedListGraph};
+
+    #[test]
+    fn dag_and_count() {
+        // two shortest paths 0 -> 1 -> 3 and 0 -> 2 -> 3
+        let g = UndirectedWeightedListGraph::from_edges(
+            5,
+            &[
+                (0, 1, 1),
+                (0, 2, 1),
+                (1, 3, 1),
+                (2, 3, 1),
+                (0, 3, 3),
+                (3, 4, 2),
+            ],
+        );
+        let dag = g.shortest_path_dag(0);
+        assert_eq!(dag.edge_count(), 5);
+        assert_eq!(
+            g.count_shortest_paths(0, 1_000_000_007),
+            Ok(vec![1, 1, 1, 2, 2])
+        );
+        assert_eq!(g.edges_on_shortest_paths(0, 3), vec![0, 1, 2, 3]);
+        assert_eq!(g.edges_on_shortest_paths(4, 0), vec![0, 1, 2, 3, 5]);
+
+        // cycle of weight zero
+        let g = DirectedWeightedListGraph::from_edges(3, &[(0, 1, 1), (1, 2, 0), (2, 1, 0)]);
+        assert!(g.count_shortest_paths(0, 998244353).is_err());
+    }
+
+    /// pop each vertex at most k times
+    fn naive(g: &[(usize, usize, u64)], n: usize, s: usize, t: usize, k: usize) -> Vec<u64> {
+        let mut popped = vec![0; n];
+        let mut res = Vec::new();
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse((0, s)));
+        while let Some(Reverse((d, u))) = heap.pop() {
+            if popped[u] == k {
+                continue;
+            }
+            popped[u] += 1;
+            if u == t {
+                res.push(d);
+            }
+            for &(a, b, w) in g {
+                if a == u {
+                    heap.push(Reverse((d + w, b)));
+                }
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn k_shortest_walks() {
+        let mut rng = test_rng(5);
+        for _ in 0..30 {
+            let n = 6;
+            let edges: Vec<_> = (0..12)
+                .map(|_| {
+                    (
+                        rng.gen_range(0, n),
+                        rng.gen_range(0, n),
+                        rng.gen_range(0, 5u64),
+                    )
+                })
+                .collect();
+            let g = DirectedWeightedListGraph::from_edges(n, &edges);
+            let (s, t) = (rng.gen_range(0, n), rng.gen_range(0, n));
+            let walks = g.k_shortest_walks(s, t, 20);
+            let lens: Vec<_> = walks.iter().map(|(d, _)| *d).collect();
+            assert_eq!(lens, naive(&edges, n, s, t, 20));
+            for (d, walk) in walks {
+                assert_eq!((walk[0], *walk.last().unwrap()), (s, t));
+                // some choice of multi-edges gives the length
+                let min: u64 = walk
+                    .windows(2)
+                    .map(|w| {
+                        edges
+                            .iter()
+                            .filter(|e| (e.0, e.1) == (w[0], w[1]))
+                            .map(|e| e.2)
+                            .min()
+                            .unwrap()
+                    })
+                    .sum();
+                assert!(min <= d);
+            }
+        }
+
+        let g = UndirectedWeightedListGraph::from_edges(3, &[(0, 1, 1u32), (1, 2, 1)]);
+        let walks = g.k_shortest_walks(0, 2, 3);
+        assert_eq!(walks[0], (2, vec![0, 1, 2]));
+        assert_eq!(walks[1].0, 4);
+        assert_eq!(walks[2].0, 4);
+    }
+}
@@ -0,0 +1,275 @@
+use std::{collections::VecDeque, mem::swap};
+
+use ::sparse_table::{Min, SparseTable};
+
+use super::UndirectedUnweightedListGraph;
+
+/// rooted tree
+///
+/// LCA can be answered both by binary lifting in `O(log n)`
+/// and by Euler tour and `SparseTable` in `O(1)`
+pub struct Tree {
+    root: usize,
+    parent: Vec<Option<usize>>,
+    depth: Vec<usize>,
+    size: Vec<usize>,
+    children: Vec<Vec<usize>>,
+    /// BFS order from the root
+    order: Vec<usize>,
+    /// `ancestor[k][v]` is the `2^k`-th ancestor of `v`, or the root if it does not exist
+    ancestor: Vec<Vec<usize>>,
+    /// first index of each vertex in the Euler tour
+    first: Vec<usize>,
+    /// `(depth, vertex)` along the Euler tour
+    euler: SparseTable<Min<(usize, usize)>>,
+}
+
+impl Tree {
+    /// `g` must be a tree, i.e. connected and has `n - 1` edges
+    pub fn new(g: &UndirectedUnweightedListGraph, root: usize) -> Self {
+        let n = g.len();
+        assert_eq!(g.edge_count() + 1, n, "not a tree");
+
+        let mut parent = vec![None; n];
+        let mut depth = vec![0; n];
+        let mut children = vec![Vec::new(); n];
+        let mut visited = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        let mut queue = VecDeque::new();
+        visited[root] = true;
+        queue.push_back(root);
+        while let Some(u) = queue.pop_front() {
+            order.push(u);
+            for &v in g.inner[u].iter() {
+                if !visited[v] {
+                    visited[v] = true;
+                    parent[v] = Some(u);
+                    depth[v] = depth[u] + 1;
+                    children[u].push(v);
+                    queue.push_back(v);
+                }
+            }
+        }
+        assert_eq!(order.len(), n, "not connected");
+
+        let mut size = vec![1; n];
+        for &v in order.iter().rev() {
+            if let Some(p) = parent[v] {
+                size[p] += size[v];
+            }
+        }
+
+        let log = std::mem::size_of::<usize>() * 8 - n.leading_zeros() as usize;
+        let mut ancestor = vec![(0..n)
+            .map(|v| parent[v].unwrap_or(root))
+            .collect::<Vec<_>>()];
+        for k in 1..log.max(1) {
+            let prev = &ancestor[k - 1];
+            let next = (0..n).map(|v| prev[prev[v]]).collect();
+            ancestor.push(next);
+        }
+
+        let mut first = vec![0; n];
+        let mut tour = Vec::with_capacity(2 * n - 1);
+        let mut stack = vec![(root, 0)];
+        while let Some((v, i)) = stack.last_mut() {
+            let v = *v;
+            if *i == 0 {
+                first[v] = tour.len();
+            }
+            tour.push((depth[v], v));
+            if let Some(&c) = children[v].get(*i) {
+                *i += 1;
+                stack.push((c, 0));
+            } else {
+                // the parent is pushed again when it resumes
+                stack.pop();
+            }
+        }
+        let euler = SparseTable::new(&tour);
+
+        Self {
+            root,
+            parent,
+            depth,
+            size,
+            children,
+            order,
+            ancestor,
+            first,
+            euler,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parent[v]
+    }
+
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    /// number of vertices in the subtree of `v`
+    pub fn subtree_size(&self, v: usize) -> usize {
+        self.size[v]
+    }
+
+    pub fn children(&self, v: usize) -> &[usize] {
+        &self.children[v]
+    }
+
+    /// BFS order, every vertex appears after its parent
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// lowest common ancestor in `O(1)` by Euler tour
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (a, b) = (self.first[u], self.first[v]);
+        let (a, b) = if a <= b { (a, b) } else { (b, a) };
+        self.euler.fold(a..=b).1
+    }
+
+    /// lowest common ancestor in `O(log n)` by binary lifting
+    pub fn lca_doubling(&self, mut u: usize, mut v: usize) -> usize {
+        if self.depth[u] < self.depth[v] {
+            swap(&mut u, &mut v);
+        }
+        u = self.kth_ancestor(u, self.depth[u] - self.depth[v]).unwrap();
+        if u == v {
+            return u;
+        }
+        for k in (0..self.ancestor.len()).rev() {
+            if self.ancestor[k][u] != self.ancestor[k][v] {
+                u = self.ancestor[k][u];
+                v = self.ancestor[k][v];
+            }
+        }
+        self.ancestor[0][u]
+    }
+
+    /// number of edges on the path between `u` and `v`
+    pub fn distance(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    /// `k`-th ancestor of `v`, `v` itself if `k == 0`
+    pub fn kth_ancestor(&self, mut v: usize, k: usize) -> Option<usize> {
+        if self.depth[v] < k {
+            return None;
+        }
+        for (i, ancestor) in self.ancestor.iter().enumerate() {
+            if k >> i & 1 == 1 {
+                v = ancestor[v];
+            }
+        }
+        Some(v)
+    }
+
+    /// `k`-th vertex on the path from `u` to `v`, `u` itself if `k == 0`
+    pub fn jump(&self, u: usize, v: usize, k: usize) -> Option<usize> {
+        let w = self.lca(u, v);
+        let du = self.depth[u] - self.depth[w];
+        let dv = self.depth[v] - self.depth[w];
+        if k <= du {
+            self.kth_ancestor(u, k)
+        } else if k <= du + dv {
+            self.kth_ancestor(v, du + dv - k)
+        } else {
+            None
+        }
+    }
+
+    /// whether `u` is an ancestor of `v` (or `v` itself)
+    pub fn is_ancestor(&self, u: usize, v: usize) -> bool {
+        self.lca(u, v) == u
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::UndirectedUnweightedListGraph;
+    use super::Tree;
+
+    //       0
+    //     / | \
+    //    1  2  3
+    //   / \    |
+    //  4   5   6
+    //      |
+    //      7
+    fn sample() -> Tree {
+        let edges = &[(0, 1), (0, 2), (0, 3), (1, 4), (1, 5), (3, 6), (5, 7)];
+        Tree::new(&UndirectedUnweightedListGraph::from_edges(8, edges), 0)
+    }
+
+    #[test]
+    fn basic() {
+        let t = sample();
+        assert_eq!(t.parent(7), Some(5));
+        assert_eq!(t.parent(0), None);
+        assert_eq!(t.depth(7), 3);
+        assert_eq!(t.subtree_size(1), 4);
+        assert_eq!(t.subtree_size(0), 8);
+        assert_eq!(t.children(1), &[4, 5]);
+        assert_eq!(t.order()[0], 0);
+    }
+
+    #[test]
+    fn lca() {
+        let t = sample();
+        for u in 0..8 {
+            for v in 0..8 {
+                assert_eq!(t.lca(u, v), t.lca_doubling(u, v));
+            }
+        }
+        assert_eq!(t.lca(4, 7), 1);
+        assert_eq!(t.lca(7, 6), 0);
+        assert_eq!(t.lca(5, 7), 5);
+        assert_eq!(t.distance(4, 6), 4);
+        assert_eq!(t.distance(7, 7), 0);
+        assert!(t.is_ancestor(1, 7));
+        assert!(!t.is_ancestor(7, 1));
+        assert!(!t.is_ancestor(2, 7));
+    }
+
+    #[test]
+    fn jump() {
+        let t = sample();
+        assert_eq!(t.kth_ancestor(7, 2), Some(1));
+        assert_eq!(t.kth_ancestor(7, 3), Some(0));
+        assert_eq!(t.kth_ancestor(7, 4), None);
+        let path = [7, 5, 1, 0, 3, 6];
+        for (k, &x) in path.iter().enumerate() {
+            assert_eq!(t.jump(7, 6, k), Some(x));
+        }
+        assert_eq!(t.jump(7, 6, 6), None);
+        assert_eq!(t.jump(4, 4, 0), Some(4));
+
+        let single = Tree::new(&UndirectedUnweightedListGraph::new(1), 0);
+        assert_eq!(single.lca(0, 0), 0);
+        assert_eq!(single.lca_doubling(0, 0), 0);
+    }
+
+    #[test]
+    fn long_path() {
+        let n = 200_000;
+        let edges: Vec<_> = (0..n - 1).map(|i| (i, i + 1)).collect();
+        let t = Tree::new(&UndirectedUnweightedListGraph::from_edges(n, &edges), 0);
+        assert_eq!(t.lca(n - 1, n / 2), n / 2);
+        assert_eq!(t.lca_doubling(n - 1, n / 2), n / 2);
+        assert_eq!(t.jump(0, n - 1, n - 2), Some(n - 2));
+    }
+}
@@ -0,0 +1,118 @@
+use super::DirectedUnweightedListGraph;
+
+/// 2-SAT solver
+///
+/// ```ignore
+/// let mut ts = TwoSat::new(2);
+/// ts.add_clause(0, true, 1, true); // x0 || x1
+/// ts.add_clause(0, false, 1, false); // !x0 || !x1
+/// assert!(ts.satisfiable());
+/// assert_ne!(ts.answer()[0], ts.answer()[1]);
+/// ```
+pub struct TwoSat {
+    n: usize,
+    answer: Vec<bool>,
+    /// vertex `2 * i + f` means `x_i == f`
+    graph: DirectedUnweightedListGraph,
+}
+
+impl TwoSat {
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            answer: vec![false; n],
+            graph: DirectedUnweightedListGraph::new(2 * n),
+        }
+    }
+
+    /// add clause `(x_i == f) || (x_j == g)`
+    pub fn add_clause(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        assert!(i < self.n && j < self.n);
+        self.graph.add_edge(2 * i + !f as usize, 2 * j + g as usize);
+        self.graph.add_edge(2 * j + !g as usize, 2 * i + f as usize);
+    }
+
+    /// whether all clauses can be satisfied at the same time,
+    /// the assignment is stored for `answer`
+    pub fn satisfiable(&mut self) -> bool {
+        let (_, ids) = self.graph.scc_ids();
+        for i in 0..self.n {
+            if ids[2 * i] == ids[2 * i + 1] {
+                return false;
+            }
+            self.answer[i] = ids[2 * i] < ids[2 * i + 1];
+        }
+        true
+    }
+
+    /// assignment found by the last `satisfiable` call which returned true
+    pub fn answer(&self) -> &[bool] {
+        &self.answer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::seq::SliceRandom;
+
+    use super::{super::random::test_rng, TwoSat};
+
+    #[test]
+    fn two_sat() {
+        // (x0 || x1), (!x0 || x2), (!x1 || !x2), (x1 || x2)
+        let clauses = &[
+            (0, true, 1, true),
+            (0, false, 2, true),
+            (1, false, 2, false),
+            (1, true, 2, true),
+        ];
+        let mut ts = TwoSat::new(3);
+        for &(i, f, j, g) in clauses {
+            ts.add_clause(i, f, j, g);
+        }
+        assert!(ts.satisfiable());
+        let ans = ts.answer();
+        assert!(clauses
+            .iter()
+            .all(|&(i, f, j, g)| ans[i] == f || ans[j] == g));
+
+        // x0 and !x0
+        let mut ts = TwoSat::new(1);
+        ts.add_clause(0, true, 0, true);
+        ts.add_clause(0, false, 0, false);
+        assert!(!ts.satisfiable());
+    }
+
+    #[test]
+    fn brute_force() {
+        // random sets of clauses over 3 variables
+        let all: Vec<_> = (0..3)
+            .flat_map(|i| (0..3).map(move |j| (i, j)))
+            .flat_map(|(i, j)| {
+                [(false, false), (false, true), (true, false), (true, true)]
+                    .iter()
+                    .map(move |&(f, g)| (i, f, j, g))
+            })
+            .collect();
+        let mut rng = test_rng(1);
+        for _ in 0..300 {
+            let clauses: Vec<_> = (0..5).map(|_| *all.choose(&mut rng).unwrap()).collect();
+            let mut ts = TwoSat::new(3);
+            for &(i, f, j, g) in clauses.iter() {
+                ts.add_clause(i, f, j, g);
+            }
+            let expected = (0..8).any(|bits: usize| {
+                clauses
+                    .iter()
+                    .all(|&(i, f, j, g)| (bits >> i & 1 == 1) == f || (bits >> j & 1 == 1) == g)
+            });
+            assert_eq!(ts.satisfiable(), expected);
+            if expected {
+                let ans = ts.answer();
+                assert!(clauses
+                    .iter()
+                    .all(|&(i, f, j, g)| ans[i] == f || ans[j] == g));
+            }
+        }
+    }
+}
@@ -55,7 +55,8 @@ impl<B: Band> From<&[B::S]> for SparseTable<B> {
 impl<B: Band> SparseTable<B> {
     pub fn new(v: &[B::S]) -> Self {
         let n = v.len();
-        let p = ceil_pow(n);
+        // at least one level for the raw values even if n == 1
+        let p = max(ceil_pow(n), 1);
         let mut inner = vec![Vec::new(); p];
         inner[0] = v.clone().to_vec();
 
@@ -120,6 +121,12 @@ mod tests {
         assert_eq!(st.fold(8..10), 3);
     }
 
+    #[test]
+    fn single() {
+        let st = SparseTable::<Min<usize>>::new(&[42]);
+        assert_eq!(st.fold(..), 42);
+    }
+
     #[test]
     fn large_random() {
         let mut rng = thread_rng();